@@ -0,0 +1,338 @@
+use crate::connectors::traits::Connector;
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::{DecodeMode, DriverError},
+};
+use crate::glyphs::{segment, GlyphTable, SevenSegment};
+use crate::MAX7219;
+
+///
+/// Rendering attributes of a single seven segment digit.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Attribute {
+    /// The digit alternates between shown and blank every `blink_ticks`
+    pub blink: bool,
+    /// The digit is only shown one frame out of `dim_ratio`, so `tick` must be
+    /// called fast enough for the skipped frames not to flicker (~100 Hz and up)
+    pub dim: bool,
+    /// Lit and unlit segments are swapped, the decimal point is left as is
+    pub inverted: bool,
+}
+
+impl Attribute {
+    /// No attribute, the digit is shown as is
+    pub const NONE: Attribute = Attribute {
+        blink: false,
+        dim: false,
+        inverted: false,
+    };
+    /// The digit blinks, e.g. while being edited
+    pub const BLINK: Attribute = Attribute {
+        blink: true,
+        dim: false,
+        inverted: false,
+    };
+}
+
+///
+/// Seven segment content with per-digit attributes, for up to `DEVICES` displays
+/// in a chain, as many as the driver it is written through.
+///
+/// The content is kept here and written through `MAX7219::write_segments`, so that
+/// `tick` only rewrites the digit registers whose visible pattern changed. Only the
+/// digits given a pattern or an attribute are owned by the text: every other digit
+/// is never written, and the decode mode is only set on the displays with an owned
+/// digit.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::delay::DelayMs;
+/// # use max7219_driver::attributes::{Attribute, AttributedText};
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, delay: &mut impl DelayMs<u32>) {
+/// let mut text = AttributedText::new();
+/// text.write_str(0, b"12:30   ", 0).unwrap();
+/// text.set_attribute(0, 3, Attribute::BLINK).unwrap();
+/// loop {
+///     text.tick(max7219).unwrap();
+///     delay.delay_ms(10_u32);
+/// }
+/// # }
+/// ```
+///
+pub struct AttributedText<const DEVICES: usize = MAX_DISPLAYS> {
+    segments: [[u8; MAX_DIGITS]; DEVICES],
+    attributes: [[Attribute; MAX_DIGITS]; DEVICES],
+    // last pattern written to each digit, None when unknown
+    shown: [[Option<u8>; MAX_DIGITS]; DEVICES],
+    // bit `position` set for every digit written by this text
+    owned: [u8; DEVICES],
+    // whether `NoDecode` was written to the display
+    decode_set: [bool; DEVICES],
+    // frame counters, kept modulo `2 * blink_ticks` and `dim_ratio`
+    blink_phase: u32,
+    dim_phase: u16,
+    blink_ticks: u16,
+    dim_ratio: u16,
+}
+
+impl<const DEVICES: usize> Default for AttributedText<DEVICES> {
+    fn default() -> Self {
+        AttributedText::with_capacity()
+    }
+}
+
+impl AttributedText {
+    ///
+    /// Creates blank content for up to `MAX_DISPLAYS` displays without attributes,
+    /// blinking every 50 ticks and showing dimmed digits one frame out of 4.
+    ///
+    pub fn new() -> Self {
+        AttributedText::with_capacity()
+    }
+}
+
+impl<const DEVICES: usize> AttributedText<DEVICES> {
+    ///
+    /// Creates blank content for up to `DEVICES` displays without attributes,
+    /// blinking every 50 ticks and showing dimmed digits one frame out of 4.
+    ///
+    pub fn with_capacity() -> Self {
+        AttributedText {
+            segments: [[0; MAX_DIGITS]; DEVICES],
+            attributes: [[Attribute::NONE; MAX_DIGITS]; DEVICES],
+            shown: [[None; MAX_DIGITS]; DEVICES],
+            owned: [0; DEVICES],
+            decode_set: [false; DEVICES],
+            blink_phase: 0,
+            dim_phase: 0,
+            blink_ticks: 50,
+            dim_ratio: 4,
+        }
+    }
+
+    ///
+    /// Sets the number of ticks a blinking digit stays on, then off.
+    ///
+    pub fn set_blink_ticks(&mut self, ticks: u16) {
+        self.blink_ticks = ticks.max(1);
+        self.blink_phase %= 2 * self.blink_ticks as u32;
+    }
+
+    ///
+    /// Sets how many frames make up one dimmed frame, a dimmed digit being
+    /// shown in only one of them.
+    ///
+    pub fn set_dim_ratio(&mut self, ratio: u16) {
+        self.dim_ratio = ratio.max(1);
+        self.dim_phase %= self.dim_ratio;
+    }
+
+    ///
+    /// Sets the segment pattern of a digit, keeping its attribute. The digit is
+    /// written by the next `tick` or `render` from then on.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `position` - character position in the range 0..=7, counted from the left
+    /// * `segments` - segment pattern in `DP A B C D E F G` order
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `device_addr` or `position` is out of range
+    ///
+    pub fn set_segments(
+        &mut self,
+        device_addr: usize,
+        position: usize,
+        segments: u8,
+    ) -> Result<(), DriverError> {
+        *slot(&mut self.segments, device_addr, position)? = segments;
+        self.owned[device_addr] |= 1 << position;
+        Ok(())
+    }
+
+    ///
+    /// Sets the attribute of a digit, keeping its content. The digit is written
+    /// by the next `tick` or `render` from then on.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `position` - character position in the range 0..=7, counted from the left
+    /// * `attribute` - the rendering attribute of the digit
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `device_addr` or `position` is out of range
+    ///
+    pub fn set_attribute(
+        &mut self,
+        device_addr: usize,
+        position: usize,
+        attribute: Attribute,
+    ) -> Result<(), DriverError> {
+        *slot(&mut self.attributes, device_addr, position)? = attribute;
+        self.owned[device_addr] |= 1 << position;
+        Ok(())
+    }
+
+    ///
+    /// Clears the attributes of every digit of the `device_addr` display.
+    ///
+    pub fn clear_attributes(&mut self, device_addr: usize) {
+        if let Some(attributes) = self.attributes.get_mut(device_addr) {
+            *attributes = [Attribute::NONE; MAX_DIGITS];
+        }
+    }
+
+    ///
+    /// Sets the content of the `device_addr` display from a byte string rendered
+    /// with the `SevenSegment` glyph table, keeping the attributes.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `string` - the byte string, 8 bytes long
+    /// * `dots` - u8 bit array specifying where to put dots in the string (1 = dot, 0 = not)
+    ///
+    pub fn write_str(
+        &mut self,
+        device_addr: usize,
+        string: &[u8; MAX_DIGITS],
+        dots: u8,
+    ) -> Result<(), DriverError> {
+        self.write_str_with(device_addr, string, dots, &SevenSegment)
+    }
+
+    ///
+    /// Sets the content of the `device_addr` display from a byte string rendered
+    /// with a custom glyph table, keeping the attributes.
+    ///
+    pub fn write_str_with<G>(
+        &mut self,
+        device_addr: usize,
+        string: &[u8; MAX_DIGITS],
+        dots: u8,
+        glyphs: &G,
+    ) -> Result<(), DriverError>
+    where
+        G: GlyphTable<Glyph = u8>,
+    {
+        let mut dot_product: u8 = 0b1000_0000;
+        for (position, b) in string.iter().enumerate() {
+            let mut segments = glyphs.encode(*b as char);
+            if (dots & dot_product) > 0 {
+                segments |= segment::DP;
+            }
+            dot_product >>= 1;
+            self.set_segments(device_addr, position, segments)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Writes every owned digit as visible in the current frame, along with the
+    /// decode mode of their displays, regardless of what was written before.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if a digit is owned on a display past
+    ///   the end of the chain
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn render<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        self.shown = [[None; MAX_DIGITS]; DEVICES];
+        self.decode_set = [false; DEVICES];
+        self.flush(max7219)
+    }
+
+    ///
+    /// Advances one frame and rewrites the digits whose visible pattern changed,
+    /// either because of an attribute or because their content was updated.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if a digit is owned on a display past
+    ///   the end of the chain
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn tick<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        self.blink_phase = (self.blink_phase + 1) % (2 * self.blink_ticks as u32);
+        self.dim_phase = (self.dim_phase + 1) % self.dim_ratio;
+        self.flush(max7219)
+    }
+
+    // pattern of a digit as visible in the current frame
+    fn visible(&self, device_addr: usize, position: usize) -> u8 {
+        let segments = self.segments[device_addr][position];
+        let attribute = self.attributes[device_addr][position];
+        let segments = if attribute.inverted {
+            !segments & !segment::DP | segments & segment::DP
+        } else {
+            segments
+        };
+        let blanked = (attribute.blink && self.blink_phase >= self.blink_ticks as u32)
+            || (attribute.dim && self.dim_phase != 0);
+        if blanked {
+            0
+        } else {
+            segments
+        }
+    }
+
+    fn flush<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        let devices = max7219.devices().min(DEVICES);
+        if self.owned[devices..].iter().any(|owned| *owned != 0) {
+            return Err(DriverError::InvalidValue);
+        }
+        for device_addr in 0..devices {
+            let owned = self.owned[device_addr];
+            if owned != 0 && !self.decode_set[device_addr] {
+                max7219.set_decode_mode(device_addr, DecodeMode::NoDecode)?;
+                self.decode_set[device_addr] = true;
+            }
+            for position in (0..MAX_DIGITS).filter(|position| owned & (1 << position) != 0) {
+                let visible = self.visible(device_addr, position);
+                if self.shown[device_addr][position] != Some(visible) {
+                    max7219.write_segments(device_addr, position, visible)?;
+                    self.shown[device_addr][position] = Some(visible);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn slot<T, const DEVICES: usize>(
+    values: &mut [[T; MAX_DIGITS]; DEVICES],
+    device_addr: usize,
+    position: usize,
+) -> Result<&mut T, DriverError> {
+    values
+        .get_mut(device_addr)
+        .and_then(|digits| digits.get_mut(position))
+        .ok_or(DriverError::InvalidValue)
+}
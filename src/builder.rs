@@ -0,0 +1,379 @@
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Write;
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::connectors::{
+    shared::{BusLock, SharedSpiConnectorCs},
+    spi::SpiConnector,
+    spi_cs::SpiConnectorCs,
+    timing::{check_spi_frequency, CsTiming},
+    traits::Connector,
+};
+use crate::global::{consts::MAX_DISPLAYS, enums::*};
+use crate::wiring::Wiring;
+use crate::MAX7219;
+
+///
+/// Configures the initial state of the displays before constructing a `MAX7219` driver.
+///
+/// Defaults match `MAX7219::from_spi` and friends: display test off, no decode,
+/// all 8 digits scanned, intensity untouched, all digits cleared and powered off.
+/// Chain-wide settings apply to every device, per-device settings override them
+/// for a single device regardless of the order they are set in.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::spi::Write;
+/// # use embedded_hal::digital::v2::OutputPin;
+/// # use max7219_driver::builder::Max7219Builder;
+/// # use max7219_driver::global::enums::{DecodeMode, Intensity, ScanLimit, Shutdown};
+/// # fn example<SPI: Write<u8>, CS: OutputPin>(spi: SPI, cs: CS) {
+/// let mut max7219 = Max7219Builder::from_spi_cs(2, spi, cs)
+///     .decode_mode(DecodeMode::CodeBDigits7_0)
+///     .device_scan_limit(1, ScanLimit::Display0To3)
+///     .intensity(Intensity::Ratio15_32)
+///     .shutdown_mode(Shutdown::NormalOperation)
+///     .build()
+///     .unwrap();
+/// # }
+/// ```
+///
+pub struct Max7219Builder<CONNECTOR> {
+    c: CONNECTOR,
+    init: bool,
+    clear: bool,
+    shutdown: Shutdown,
+    decode: DeviceSetting<DecodeMode>,
+    scan_limit: DeviceSetting<ScanLimit>,
+    intensity: DeviceSetting<Option<Intensity>>,
+    compensate_intensity: bool,
+    wiring: DeviceSetting<Wiring>,
+    spi_frequency: Option<u32>,
+    invalid_device: bool,
+}
+
+/// A chain-wide value with optional per-device overrides
+#[derive(Clone, Copy)]
+struct DeviceSetting<T: Copy> {
+    all: T,
+    devices: [Option<T>; MAX_DISPLAYS],
+}
+
+impl<T: Copy> DeviceSetting<T> {
+    fn new(all: T) -> Self {
+        DeviceSetting {
+            all,
+            devices: [None; MAX_DISPLAYS],
+        }
+    }
+
+    fn get(&self, device_addr: usize) -> T {
+        self.devices[device_addr].unwrap_or(self.all)
+    }
+}
+
+impl<CONNECTOR> Max7219Builder<CONNECTOR>
+where
+    CONNECTOR: Connector,
+{
+    pub(crate) fn new(connector: CONNECTOR) -> Self {
+        Max7219Builder {
+            c: connector,
+            init: true,
+            clear: true,
+            shutdown: Shutdown::ShutdownMode,
+            decode: DeviceSetting::new(DecodeMode::NoDecode),
+            scan_limit: DeviceSetting::new(ScanLimit::Display0To7),
+            intensity: DeviceSetting::new(None),
+            compensate_intensity: false,
+            wiring: DeviceSetting::new(Wiring::STANDARD),
+            spi_frequency: None,
+            invalid_device: false,
+        }
+    }
+
+    ///
+    /// Attach to the displays without writing any register, so that they keep
+    /// their current configuration and content, e.g. across a firmware restart.
+    /// The CS pin is still put into its idle state, and the wiring and intensity
+    /// compensation settings are kept by the driver, as they are not stored in
+    /// the displays. The register settings of the builder are ignored.
+    ///
+    pub fn skip_init(mut self) -> Self {
+        self.init = false;
+        self
+    }
+
+    ///
+    /// Whether to clear all digits during init, defaults to `true`.
+    ///
+    pub fn clear(mut self, clear: bool) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    ///
+    /// Power state of all displays after init, defaults to `Shutdown::ShutdownMode`.
+    ///
+    pub fn shutdown_mode(mut self, mode: Shutdown) -> Self {
+        self.shutdown = mode;
+        self
+    }
+
+    ///
+    /// Decode mode of all displays, defaults to `DecodeMode::NoDecode`.
+    ///
+    pub fn decode_mode(mut self, mode: DecodeMode) -> Self {
+        self.decode.all = mode;
+        self
+    }
+
+    ///
+    /// Decode mode of the `device_addr` display.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `mode` - one of the options in the `DecodeMode` enum
+    ///
+    pub fn device_decode_mode(mut self, device_addr: usize, mode: DecodeMode) -> Self {
+        self.set_device(device_addr, |b| &mut b.decode.devices, mode);
+        self
+    }
+
+    ///
+    /// Scan limit of all displays, defaults to `ScanLimit::Display0To7`.
+    ///
+    pub fn scan_limit(mut self, mode: ScanLimit) -> Self {
+        self.scan_limit.all = mode;
+        self
+    }
+
+    ///
+    /// Scan limit of the `device_addr` display.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `mode` - one of the options in the `ScanLimit` enum
+    ///
+    pub fn device_scan_limit(mut self, device_addr: usize, mode: ScanLimit) -> Self {
+        self.set_device(device_addr, |b| &mut b.scan_limit.devices, mode);
+        self
+    }
+
+    ///
+    /// Intensity of all displays, left untouched by default.
+    ///
+    pub fn intensity(mut self, mode: Intensity) -> Self {
+        self.intensity.all = Some(mode);
+        self
+    }
+
+    ///
+    /// Intensity of the `device_addr` display.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `mode` - one of the options in the `Intensity` enum
+    ///
+    pub fn device_intensity(mut self, device_addr: usize, mode: Intensity) -> Self {
+        self.set_device(device_addr, |b| &mut b.intensity.devices, Some(mode));
+        self
+    }
+
+    ///
+    /// Whether to compensate the intensity for the scan limit of each display,
+    /// defaults to `false`. See `MAX7219::set_intensity_compensation`.
+    ///
+    pub fn intensity_compensation(mut self, enabled: bool) -> Self {
+        self.compensate_intensity = enabled;
+        self
+    }
+
+    ///
+    /// Digit and segment wiring of all displays, defaults to `Wiring::STANDARD`.
+    ///
+    pub fn wiring(mut self, wiring: Wiring) -> Self {
+        self.wiring.all = wiring;
+        self
+    }
+
+    ///
+    /// Digit and segment wiring of the `device_addr` display.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `wiring` - the digit and segment wiring of the display
+    ///
+    pub fn device_wiring(mut self, device_addr: usize, wiring: Wiring) -> Self {
+        self.set_device(device_addr, |b| &mut b.wiring.devices, wiring);
+        self
+    }
+
+    ///
+    /// The SPI clock frequency the bus was configured with, checked against the
+    /// 10 MHz maximum of the chips on build. Not checked unless set, as
+    /// embedded-hal does not expose it.
+    ///
+    pub fn spi_frequency(mut self, hz: u32) -> Self {
+        self.spi_frequency = Some(hz);
+        self
+    }
+
+    ///
+    /// Construct the driver, initializing the displays unless `skip_init` was used.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if a per-device setting addressed a
+    ///   display outside the chain, or the SPI frequency is above 10 MHz
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn build(self) -> Result<MAX7219<CONNECTOR>, DriverError> {
+        let devices = self.c.devices();
+        if let Some(hz) = self.spi_frequency {
+            check_spi_frequency(hz)?;
+        }
+        if devices > MAX_DISPLAYS || self.invalid_device {
+            return Err(DriverError::InvalidValue);
+        }
+        if self
+            .decode
+            .devices
+            .iter()
+            .zip(self.scan_limit.devices.iter())
+            .zip(self.intensity.devices.iter())
+            .zip(self.wiring.devices.iter())
+            .skip(devices)
+            .any(|(((d, s), i), w)| d.is_some() || s.is_some() || i.is_some() || w.is_some())
+        {
+            return Err(DriverError::InvalidValue);
+        }
+
+        let mut max7219 = MAX7219::new(self.c);
+        for i in 0..devices {
+            max7219.wiring[i] = self.wiring.get(i);
+        }
+        max7219.compensate_intensity = [self.compensate_intensity; MAX_DISPLAYS];
+        max7219.c.init()?;
+        if !self.init {
+            return Ok(max7219);
+        }
+
+        for i in 0..devices {
+            max7219.set_display_test_mode(i, DisplayTest::NormalOperationMode)?; // turn testmode off
+            max7219.set_decode_mode(i, self.decode.get(i))?;
+            max7219.set_scan_limit(i, self.scan_limit.get(i))?;
+            if let Some(intensity) = self.intensity.get(i) {
+                max7219.set_intensity(i, intensity)?;
+            }
+            if self.clear {
+                max7219.clear_display(i)?; // clear all digits
+            }
+        }
+        max7219.set_shutdown_mode(self.shutdown)?;
+        Ok(max7219)
+    }
+
+    fn set_device<T: Copy>(
+        &mut self,
+        device_addr: usize,
+        field: impl FnOnce(&mut Self) -> &mut [Option<T>; MAX_DISPLAYS],
+        value: T,
+    ) {
+        match field(self).get_mut(device_addr) {
+            Some(slot) => *slot = Some(value),
+            None => self.invalid_device = true,
+        }
+    }
+}
+
+impl<SPI> Max7219Builder<SpiConnector<SPI>>
+where
+    SPI: Write<u8>,
+{
+    ///
+    /// Start configuring a driver in full hardware CS mode, see `MAX7219::from_spi`.
+    ///
+    pub fn from_spi(displays: usize, spi: SPI) -> Self {
+        Max7219Builder::from_spi_chip(displays, spi, Chip::Max7219)
+    }
+
+    ///
+    /// Start configuring a driver in full hardware CS mode for the given chip variant,
+    /// see `MAX7219::from_spi_chip`.
+    ///
+    pub fn from_spi_chip(displays: usize, spi: SPI, chip: Chip) -> Self {
+        Max7219Builder::new(SpiConnector::new(displays, spi, chip))
+    }
+}
+
+impl<SPI, CS> Max7219Builder<SpiConnectorCs<SPI, CS>>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    ///
+    /// Start configuring a driver with a manually controlled CS pin, see `MAX7219::from_spi_cs`.
+    ///
+    pub fn from_spi_cs(displays: usize, spi: SPI, cs: CS) -> Self {
+        Max7219Builder::from_spi_cs_chip(displays, spi, cs, Chip::Max7219)
+    }
+
+    ///
+    /// Start configuring a driver with a manually controlled CS pin for the given chip
+    /// variant, see `MAX7219::from_spi_cs_chip`.
+    ///
+    pub fn from_spi_cs_chip(displays: usize, spi: SPI, cs: CS, chip: Chip) -> Self {
+        Max7219Builder::new(SpiConnectorCs::new(displays, spi, cs, chip))
+    }
+}
+
+impl<SPI, CS, DELAY> Max7219Builder<SpiConnectorCs<SPI, CS, DELAY>>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+    DELAY: DelayUs<u32>,
+{
+    ///
+    /// Start configuring a driver with a manually controlled CS pin and enforced
+    /// CS timings, see `MAX7219::from_spi_cs_timed`.
+    ///
+    pub fn from_spi_cs_timed(
+        displays: usize,
+        spi: SPI,
+        cs: CS,
+        chip: Chip,
+        delay: DELAY,
+        timing: CsTiming,
+    ) -> Self {
+        Max7219Builder::new(SpiConnectorCs::new_timed(
+            displays, spi, cs, chip, delay, timing,
+        ))
+    }
+}
+
+impl<BUS, CS> Max7219Builder<SharedSpiConnectorCs<BUS, CS>>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    ///
+    /// Start configuring a driver on a shared SPI bus, see `MAX7219::from_shared_spi_cs`.
+    ///
+    pub fn from_shared_spi_cs(displays: usize, bus: BUS, cs: CS) -> Self {
+        Max7219Builder::from_shared_spi_cs_chip(displays, bus, cs, Chip::Max7219)
+    }
+
+    ///
+    /// Start configuring a driver on a shared SPI bus for the given chip variant,
+    /// see `MAX7219::from_shared_spi_cs_chip`.
+    ///
+    pub fn from_shared_spi_cs_chip(displays: usize, bus: BUS, cs: CS, chip: Chip) -> Self {
+        Max7219Builder::new(SharedSpiConnectorCs::new(displays, bus, cs, chip))
+    }
+}
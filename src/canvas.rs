@@ -0,0 +1,411 @@
+//!
+//! Drawing primitives on a matrix framebuffer, for simple user interfaces without
+//! an external graphics crate.
+//!
+
+use crate::global::{consts::MAX_DISPLAYS, enums::DriverError};
+use crate::matrix::{Framebuffer, Rect};
+
+///
+/// A 1 bit per pixel image, stored row by row with bit 7 of each byte the leftmost
+/// pixel, each row starting on a new byte.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitmap<'a> {
+    data: &'a [u8],
+    width: i32,
+    height: i32,
+}
+
+impl<'a> Bitmap<'a> {
+    ///
+    /// Creates a bitmap of `width` x `height` pixels over `data`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `data` is shorter than
+    ///   `width.div_ceil(8) * height` bytes
+    ///
+    pub fn new(data: &'a [u8], width: u16, height: u16) -> Result<Self, DriverError> {
+        if data.len() < (width as usize).div_ceil(8) * height as usize {
+            return Err(DriverError::InvalidValue);
+        }
+        Ok(Bitmap {
+            data,
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    ///
+    /// Get the width in pixels.
+    ///
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    ///
+    /// Get the height in pixels.
+    ///
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    ///
+    /// Get the state of a pixel, `false` outside of the bitmap.
+    ///
+    pub fn pixel(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return false;
+        }
+        let stride = (self.width as usize).div_ceil(8);
+        let byte = self.data[y as usize * stride + x as usize / 8];
+        byte & (0b1000_0000 >> (x % 8)) != 0
+    }
+}
+
+///
+/// Draws into an area of a framebuffer.
+///
+/// Coordinates are relative to the top left corner of the area, and everything drawn
+/// is clipped to the area.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::canvas::Canvas;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::{Framebuffer, Rect};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>) {
+/// let mut frame = Framebuffer::new(4, 1).unwrap();
+/// let mut canvas = Canvas::new(&mut frame);
+/// canvas.rect(Rect::new(0, 0, 32, 8), true);
+/// canvas.line(0, 0, 31, 7, true);
+/// canvas.circle(16, 4, 3, true);
+/// max7219.write_frame(&frame).unwrap();
+/// # }
+/// ```
+///
+pub struct Canvas<'a, const MODULES: usize = MAX_DISPLAYS> {
+    frame: &'a mut Framebuffer<MODULES>,
+    area: Rect,
+}
+
+impl<'a, const MODULES: usize> Canvas<'a, MODULES> {
+    ///
+    /// Creates a canvas covering the whole framebuffer.
+    ///
+    pub fn new(frame: &'a mut Framebuffer<MODULES>) -> Self {
+        let area = frame.bounds();
+        Canvas { frame, area }
+    }
+
+    ///
+    /// Creates a canvas covering `area` of the framebuffer, clipped to its bounds.
+    ///
+    pub fn with_area(frame: &'a mut Framebuffer<MODULES>, area: Rect) -> Self {
+        let area = area.intersect(frame.bounds());
+        Canvas { frame, area }
+    }
+
+    ///
+    /// Get the area of the framebuffer drawn into.
+    ///
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    ///
+    /// Get the width in pixels.
+    ///
+    pub fn width(&self) -> i32 {
+        self.area.width
+    }
+
+    ///
+    /// Get the height in pixels.
+    ///
+    pub fn height(&self) -> i32 {
+        self.area.height
+    }
+
+    ///
+    /// Get the state of a pixel, `false` outside of the canvas.
+    ///
+    pub fn pixel(&self, x: i32, y: i32) -> bool {
+        self.contains(x, y) && self.frame.pixel(self.area.x + x, self.area.y + y)
+    }
+
+    ///
+    /// Sets the state of a pixel, ignored outside of the canvas.
+    ///
+    pub fn set_pixel(&mut self, x: i32, y: i32, on: bool) {
+        if self.contains(x, y) {
+            self.frame.set_pixel(self.area.x + x, self.area.y + y, on);
+        }
+    }
+
+    ///
+    /// Sets every pixel of the canvas.
+    ///
+    pub fn fill(&mut self, on: bool) {
+        self.fill_rect(Rect::new(0, 0, self.width(), self.height()), on);
+    }
+
+    ///
+    /// Draws a line from `(x0, y0)` to `(x1, y1)`, both ends included.
+    ///
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, on: bool) {
+        // only the part within the canvas is iterated, however far away the ends are
+        let Some(((x0, y0), (x1, y1))) = clip_line((x0, y0), (x1, y1), self.width(), self.height())
+        else {
+            return;
+        };
+        // Bresenham's algorithm, for all octants, saturating for far away ends
+        let (dx, dy) = (
+            x1.saturating_sub(x0).saturating_abs(),
+            y1.saturating_sub(y0).saturating_abs().saturating_neg(),
+        );
+        let (step_x, step_y) = (x1.cmp(&x0) as i32, y1.cmp(&y0) as i32);
+        let (mut x, mut y, mut error) = (x0, y0, dx.saturating_add(dy));
+        loop {
+            self.set_pixel(x, y, on);
+            if x == x1 && y == y1 {
+                break;
+            }
+            if error.saturating_mul(2) >= dy && x != x1 {
+                error = error.saturating_add(dy);
+                x += step_x;
+            }
+            if error.saturating_mul(2) <= dx && y != y1 {
+                error = error.saturating_add(dx);
+                y += step_y;
+            }
+        }
+    }
+
+    ///
+    /// Draws the outline of `rect`.
+    ///
+    pub fn rect(&mut self, rect: Rect, on: bool) {
+        if rect.width <= 0 || rect.height <= 0 {
+            return;
+        }
+        let (right, bottom) = (rect.x + rect.width - 1, rect.y + rect.height - 1);
+        self.line(rect.x, rect.y, right, rect.y, on);
+        self.line(rect.x, bottom, right, bottom, on);
+        self.line(rect.x, rect.y, rect.x, bottom, on);
+        self.line(right, rect.y, right, bottom, on);
+    }
+
+    ///
+    /// Sets every pixel of `rect`.
+    ///
+    pub fn fill_rect(&mut self, rect: Rect, on: bool) {
+        let rect = rect.intersect(Rect::new(0, 0, self.width(), self.height()));
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                self.set_pixel(x, y, on);
+            }
+        }
+    }
+
+    ///
+    /// Draws the outline of a circle centred on `(cx, cy)`.
+    ///
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32, on: bool) {
+        // midpoint circle algorithm, one octant mirrored eight times
+        let (mut x, mut y, mut error) = (radius, 0, 1 - radius);
+        while x >= y {
+            for (px, py) in [
+                (x, y),
+                (y, x),
+                (-y, x),
+                (-x, y),
+                (-x, -y),
+                (-y, -x),
+                (y, -x),
+                (x, -y),
+            ] {
+                self.set_pixel(cx + px, cy + py, on);
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    ///
+    /// Draws `bitmap` with its top left corner at `(x, y)`.
+    /// Only lit pixels are drawn, the background is left as is.
+    ///
+    pub fn blit(&mut self, x: i32, y: i32, bitmap: &Bitmap) {
+        for by in 0..bitmap.height() {
+            for bx in 0..bitmap.width() {
+                if bitmap.pixel(bx, by) {
+                    self.set_pixel(x + bx, y + by, true);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Inverts every pixel of the canvas.
+    ///
+    pub fn invert(&mut self) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let on = self.pixel(x, y);
+                self.set_pixel(x, y, !on);
+            }
+        }
+    }
+
+    ///
+    /// Moves the contents by `dx` pixels to the right and `dy` pixels down,
+    /// blanking the pixels left behind.
+    ///
+    pub fn shift(&mut self, dx: i32, dy: i32) {
+        self.move_contents(dx, dy, false);
+    }
+
+    ///
+    /// Moves the contents by `dx` pixels to the right and `dy` pixels down,
+    /// wrapping the pixels moved out of one edge around to the opposite one.
+    ///
+    pub fn rotate(&mut self, dx: i32, dy: i32) {
+        self.move_contents(dx, dy, true);
+    }
+
+    ///
+    /// Sets the pixels connected to `(x, y)` that are in the same state as it,
+    /// up, down, left and right.
+    ///
+    pub fn flood_fill(&mut self, x: i32, y: i32, on: bool) {
+        if !self.contains(x, y) || self.pixel(x, y) == on {
+            return;
+        }
+        // one bit per pixel of the canvas, which is never larger than the framebuffer;
+        // sweeping forwards and backwards until nothing changes reaches every connected pixel
+        let mut filled = [0u64; MODULES];
+        let pixels = self.width() * self.height();
+        let index_of = |px: i32, py: i32| (py * self.width() + px) as usize;
+        let is_filled =
+            |filled: &[u64; MODULES], index: usize| filled[index / 64] & (1 << (index % 64)) != 0;
+        filled[index_of(x, y) / 64] |= 1 << (index_of(x, y) % 64);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for forward in [true, false] {
+                for index in 0..pixels {
+                    let index = if forward { index } else { pixels - 1 - index };
+                    let (px, py) = (index % self.width(), index / self.width());
+                    if is_filled(&filled, index as usize) || self.pixel(px, py) == on {
+                        continue;
+                    }
+                    let touches = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| {
+                        self.contains(px + dx, py + dy)
+                            && is_filled(&filled, index_of(px + dx, py + dy))
+                    });
+                    if touches {
+                        filled[index as usize / 64] |= 1 << (index % 64);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        for index in 0..pixels {
+            if is_filled(&filled, index as usize) {
+                self.set_pixel(index % self.width(), index / self.width(), on);
+            }
+        }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.area.width && y < self.area.height
+    }
+
+    fn move_contents(&mut self, dx: i32, dy: i32, wrap: bool) {
+        let source = self.frame.clone();
+        let (width, height) = (self.width(), self.height());
+        for y in 0..height {
+            for x in 0..width {
+                let (mut sx, mut sy) = (x - dx, y - dy);
+                if wrap {
+                    sx = sx.rem_euclid(width);
+                    sy = sy.rem_euclid(height);
+                }
+                let on = sx >= 0
+                    && sy >= 0
+                    && sx < width
+                    && sy < height
+                    && source.pixel(self.area.x + sx, self.area.y + sy);
+                self.set_pixel(x, y, on);
+            }
+        }
+    }
+}
+
+// Cohen-Sutherland clipping of a line to a `width` x `height` area at the origin,
+// `None` if the line misses the area
+fn clip_line(
+    start: (i32, i32),
+    end: (i32, i32),
+    width: i32,
+    height: i32,
+) -> Option<((i32, i32), (i32, i32))> {
+    const LEFT: u8 = 1;
+    const RIGHT: u8 = 2;
+    const TOP: u8 = 4;
+    const BOTTOM: u8 = 8;
+    let (right, bottom) = (width as i128 - 1, height as i128 - 1);
+    let outcode = |(x, y): (i128, i128)| {
+        let mut code = 0;
+        if x < 0 {
+            code |= LEFT;
+        } else if x > right {
+            code |= RIGHT;
+        }
+        if y < 0 {
+            code |= TOP;
+        } else if y > bottom {
+            code |= BOTTOM;
+        }
+        code
+    };
+    let (mut p0, mut p1) = (
+        (start.0 as i128, start.1 as i128),
+        (end.0 as i128, end.1 as i128),
+    );
+    loop {
+        let (code0, code1) = (outcode(p0), outcode(p1));
+        if code0 | code1 == 0 {
+            return Some(((p0.0 as i32, p0.1 as i32), (p1.0 as i32, p1.1 as i32)));
+        }
+        if code0 & code1 != 0 || width <= 0 || height <= 0 {
+            return None;
+        }
+        let code = if code0 != 0 { code0 } else { code1 };
+        let ((x0, y0), (x1, y1)) = (p0, p1);
+        // the line crosses the edge, so it is not parallel to it
+        let point = if code & TOP != 0 {
+            (x0 + (x1 - x0) * -y0 / (y1 - y0), 0)
+        } else if code & BOTTOM != 0 {
+            (x0 + (x1 - x0) * (bottom - y0) / (y1 - y0), bottom)
+        } else if code & LEFT != 0 {
+            (0, y0 + (y1 - y0) * -x0 / (x1 - x0))
+        } else {
+            (right, y0 + (y1 - y0) * (right - x0) / (x1 - x0))
+        };
+        if code == code0 {
+            p0 = point;
+        } else {
+            p1 = point;
+        }
+    }
+}
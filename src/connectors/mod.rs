@@ -1,3 +1,6 @@
 pub mod traits;
+pub mod multi;
+pub mod shared;
 pub mod spi;
 pub mod spi_cs;
+pub mod timing;
@@ -0,0 +1,87 @@
+use crate::global::enums::{Chip, DriverError};
+
+use super::traits::Connector;
+
+///
+/// Two independent chains presented as one, e.g. on separate CS lines or SPI buses.
+///
+/// Devices of the first chain come first in the device index space, followed by
+/// the devices of the second chain. More chains are combined by nesting, e.g.
+/// `MultiConnector<A, MultiConnector<B, C>>`. Chain writes are split so that each
+/// chain only receives its own part of a row, keeping full refreshes row-parallel
+/// on every chain.
+///
+pub struct MultiConnector<FIRST, SECOND>
+where
+    FIRST: Connector,
+    SECOND: Connector,
+{
+    first: FIRST,
+    second: SECOND,
+}
+
+impl<FIRST, SECOND> MultiConnector<FIRST, SECOND>
+where
+    FIRST: Connector,
+    SECOND: Connector,
+{
+    pub(crate) fn new(first: FIRST, second: SECOND) -> Self {
+        MultiConnector { first, second }
+    }
+
+    ///
+    /// Releases both chain connectors.
+    ///
+    pub fn release(self) -> (FIRST, SECOND) {
+        (self.first, self.second)
+    }
+}
+
+impl<FIRST, SECOND> Connector for MultiConnector<FIRST, SECOND>
+where
+    FIRST: Connector,
+    SECOND: Connector,
+{
+    #[inline]
+    fn devices(&self) -> usize {
+        self.first.devices() + self.second.devices()
+    }
+
+    fn chip(&self, device_addr: usize) -> Chip {
+        let first_devices = self.first.devices();
+        if device_addr < first_devices {
+            self.first.chip(device_addr)
+        } else {
+            self.second.chip(device_addr - first_devices)
+        }
+    }
+
+    fn init(&mut self) -> Result<(), DriverError> {
+        self.first.init()?;
+        self.second.init()
+    }
+
+    fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        if frame.len() != self.serial_data_max_bytes() {
+            return Err(DriverError::InvalidValue);
+        }
+        let (first, second) = frame.split_at(self.first.serial_data_max_bytes());
+        self.first.write_chain(first)?;
+        self.second.write_chain(second)
+    }
+
+    fn write_raw(
+        &mut self,
+        device_addr: usize,
+        register_addr: u8,
+        data: u8,
+    ) -> Result<(), DriverError> {
+        let first_devices = self.first.devices();
+        if device_addr < first_devices {
+            self.first.write_raw(device_addr, register_addr, data)
+        } else {
+            self.second
+                .write_raw(device_addr - first_devices, register_addr, data)
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use core::cell::RefCell;
+
+use embedded_hal::blocking::spi::Write;
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::global::{
+    consts::MAX_SERIAL_DATA_BYTES,
+    enums::{Chip, DriverError},
+};
+
+use super::spi::fill_chain_frame;
+use super::traits::Connector;
+
+///
+/// Grants exclusive access to an SPI bus shared with other drivers.
+///
+/// The bus is only held for the duration of `lock`, which `SharedSpiConnectorCs`
+/// calls once per chain write.
+///
+pub trait BusLock {
+    /// The SPI bus behind the lock
+    type Bus: Write<u8>;
+
+    ///
+    /// Runs `f` with exclusive access to the bus.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::Bus` - returned if the bus is currently held elsewhere
+    ///
+    fn lock<R>(&self, f: impl FnOnce(&mut Self::Bus) -> R) -> Result<R, DriverError>;
+}
+
+impl<SPI> BusLock for &RefCell<SPI>
+where
+    SPI: Write<u8>,
+{
+    type Bus = SPI;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut SPI) -> R) -> Result<R, DriverError> {
+        let mut bus = self.try_borrow_mut().map_err(|_| DriverError::Bus)?;
+        Ok(f(&mut bus))
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl<SPI> BusLock for &critical_section::Mutex<RefCell<SPI>>
+where
+    SPI: Write<u8>,
+{
+    type Bus = SPI;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut SPI) -> R) -> Result<R, DriverError> {
+        critical_section::with(|cs| {
+            let mut bus = self
+                .borrow(cs)
+                .try_borrow_mut()
+                .map_err(|_| DriverError::Bus)?;
+            Ok(f(&mut bus))
+        })
+    }
+}
+
+/// Software controlled CS connector over a shared SPI bus
+pub struct SharedSpiConnectorCs<BUS, CS>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    devices: usize,
+    chip: Chip,
+    buffer: [u8; MAX_SERIAL_DATA_BYTES],
+    bus: BUS,
+    cs: CS,
+}
+
+impl<BUS, CS> SharedSpiConnectorCs<BUS, CS>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    pub(crate) fn new(displays: usize, bus: BUS, cs: CS, chip: Chip) -> Self {
+        SharedSpiConnectorCs {
+            devices: displays,
+            chip,
+            buffer: [0; MAX_SERIAL_DATA_BYTES],
+            bus,
+            cs,
+        }
+    }
+
+    ///
+    /// Releases the bus handle and CS pin.
+    ///
+    pub fn release(self) -> (BUS, CS) {
+        (self.bus, self.cs)
+    }
+}
+
+impl<BUS, CS> Connector for SharedSpiConnectorCs<BUS, CS>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    #[inline]
+    fn devices(&self) -> usize {
+        self.devices
+    }
+
+    #[inline]
+    fn chip(&self, _device_addr: usize) -> Chip {
+        self.chip
+    }
+
+    fn init(&mut self) -> Result<(), DriverError> {
+        match self.chip {
+            // CS idles high, a MAX7221 ignores DIN until its first falling edge
+            Chip::Max7221 => self.cs.set_high().map_err(|_| DriverError::Pin),
+            // LOAD only latches on its rising edge after each chain write
+            Chip::Max7219 => Ok(()),
+        }
+    }
+
+    fn write_raw(
+        &mut self,
+        device_addr: usize,
+        register_addr: u8,
+        data: u8,
+    ) -> Result<(), DriverError> {
+        fill_chain_frame(&mut self.buffer, device_addr, register_addr, data);
+        let frame = &self.buffer[0..self.devices * 2];
+        write_locked(&self.bus, &mut self.cs, frame)
+    }
+
+    fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        if frame.len() != self.serial_data_max_bytes() {
+            return Err(DriverError::InvalidValue);
+        }
+        write_locked(&self.bus, &mut self.cs, frame)
+    }
+}
+
+// writes one chain frame framed by CS while holding the bus
+fn write_locked<BUS, CS>(bus: &BUS, cs: &mut CS, frame: &[u8]) -> Result<(), DriverError>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    bus.lock(|spi| {
+        cs.set_low().map_err(|_| DriverError::Pin)?;
+        spi.write(frame).map_err(|_| DriverError::Spi)?;
+        cs.set_high().map_err(|_| DriverError::Pin)
+    })?
+}
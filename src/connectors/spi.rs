@@ -1,14 +1,18 @@
-use embedded_hal::blocking::spi::Write;
+use embedded_hal::blocking::spi::{Transfer, Write};
 
-use crate::global::{consts::MAX_SERIAL_DATA_BYTES, enums::DriverError};
+use crate::global::{
+    consts::MAX_SERIAL_DATA_BYTES,
+    enums::{Chip, DriverError},
+};
 
-use super::traits::Connector;
+use super::traits::{Connector, LoopbackConnector};
 
 pub struct SpiConnector<SPI>
 where
     SPI: Write<u8>,
 {
     devices: usize,
+    chip: Chip,
     buffer: [u8; MAX_SERIAL_DATA_BYTES],
     spi: SPI,
 }
@@ -18,13 +22,21 @@ impl<SPI> SpiConnector<SPI>
 where
     SPI: Write<u8>,
 {
-    pub(crate) fn new(displays: usize, spi: SPI) -> Self {
+    pub(crate) fn new(displays: usize, spi: SPI, chip: Chip) -> Self {
         SpiConnector {
             devices: displays,
+            chip,
             buffer: [0; MAX_SERIAL_DATA_BYTES],
             spi,
         }
     }
+
+    ///
+    /// Releases the SPI interface.
+    ///
+    pub fn release(self) -> SPI {
+        self.spi
+    }
 }
 
 impl<SPI> Connector for SpiConnector<SPI>
@@ -35,22 +47,55 @@ where
     fn devices(&self) -> usize {
         self.devices
     }
+    #[inline]
+    fn chip(&self, _device_addr: usize) -> Chip {
+        self.chip
+    }
     fn write_raw(
         &mut self,
         device_addr: usize,
         register_addr: u8,
         data: u8,
     ) -> Result<(), DriverError> {
-        // Each device has two elements([register_addr,data]) need * 2
-        let offset = device_addr * 2;
-        self.buffer = [0; MAX_SERIAL_DATA_BYTES];
-        // set register_addr and data
-        self.buffer[offset] = register_addr;
-        self.buffer[offset + 1] = data;
+        fill_chain_frame(&mut self.buffer, device_addr, register_addr, data);
         self.spi
             .write(&self.buffer[0..self.serial_data_max_bytes()])
             .map_err(|_| DriverError::Spi)?;
 
         Ok(())
     }
+    fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        if frame.len() != self.serial_data_max_bytes() {
+            return Err(DriverError::InvalidValue);
+        }
+        self.spi.write(frame).map_err(|_| DriverError::Spi)
+    }
+}
+
+impl<SPI> LoopbackConnector for SpiConnector<SPI>
+where
+    SPI: Write<u8> + Transfer<u8>,
+{
+    fn transfer_raw(&mut self, buffer: &mut [u8]) -> Result<(), DriverError> {
+        self.spi.transfer(buffer).map_err(|_| DriverError::Spi)?;
+        Ok(())
+    }
+}
+
+///
+/// Fills the chain buffer so that only `device_addr` receives `register_addr` and `data`,
+/// every other device in the chain receives a no-op.
+///
+pub(crate) fn fill_chain_frame(
+    buffer: &mut [u8; MAX_SERIAL_DATA_BYTES],
+    device_addr: usize,
+    register_addr: u8,
+    data: u8,
+) {
+    // Each device has two elements([register_addr,data]) need * 2
+    let offset = device_addr * 2;
+    *buffer = [0; MAX_SERIAL_DATA_BYTES];
+    // set register_addr and data
+    buffer[offset] = register_addr;
+    buffer[offset + 1] = data;
 }
@@ -1,19 +1,30 @@
-use embedded_hal::blocking::spi::Write;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::{Transfer, Write};
 use embedded_hal::digital::v2::OutputPin;
 
-use crate::global::enums::DriverError;
+use crate::global::enums::{Chip, DriverError};
 
 use super::spi::SpiConnector;
-use super::traits::Connector;
+use super::timing::{delay_ns, CsTiming, NoDelay};
+use super::traits::{Connector, LoopbackConnector};
 
 /// Software controlled CS connector with SPI transfer
-pub struct SpiConnectorCs<SPI, CS>
+///
+/// `CS` is driven low for every chain write and high once the chain has been
+/// written, its rising edge latching the data. A MAX7221's `CS` is also set high
+/// on init, as it ignores the serial input until the first falling edge, see `Chip`.
+/// With a delay provider other than `NoDelay`, the `CsTiming` minimums are
+/// enforced around every chain write.
+pub struct SpiConnectorCs<SPI, CS, DELAY = NoDelay>
 where
     SPI: Write<u8>,
     CS: OutputPin,
+    DELAY: DelayUs<u32>,
 {
     spi_c: SpiConnector<SPI>,
     cs: CS,
+    delay: DELAY,
+    timing: CsTiming,
 }
 
 impl<SPI, CS> SpiConnectorCs<SPI, CS>
@@ -21,34 +32,123 @@ where
     SPI: Write<u8>,
     CS: OutputPin,
 {
-    pub(crate) fn new(displays: usize, spi: SPI, cs: CS) -> Self {
+    pub(crate) fn new(displays: usize, spi: SPI, cs: CS, chip: Chip) -> Self {
+        SpiConnectorCs::new_timed(displays, spi, cs, chip, NoDelay, CsTiming::DATASHEET)
+    }
+}
+
+impl<SPI, CS, DELAY> SpiConnectorCs<SPI, CS, DELAY>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+    DELAY: DelayUs<u32>,
+{
+    pub(crate) fn new_timed(
+        displays: usize,
+        spi: SPI,
+        cs: CS,
+        chip: Chip,
+        delay: DELAY,
+        timing: CsTiming,
+    ) -> Self {
         SpiConnectorCs {
-            spi_c: SpiConnector::new(displays, spi),
+            spi_c: SpiConnector::new(displays, spi, chip),
             cs,
+            delay,
+            timing,
         }
     }
+
+    ///
+    /// Releases the SPI interface and CS pin.
+    ///
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi_c.release(), self.cs)
+    }
+
+    ///
+    /// Releases the SPI interface, CS pin and delay provider.
+    ///
+    pub fn release_with_delay(self) -> (SPI, CS, DELAY) {
+        (self.spi_c.release(), self.cs, self.delay)
+    }
+
+    // starts a chain write: a MAX7221 only shifts data in while CS is low
+    fn select(&mut self) -> Result<(), DriverError> {
+        self.cs.set_low().map_err(|_| DriverError::Pin)?;
+        delay_ns(&mut self.delay, self.timing.cs_setup_ns);
+        Ok(())
+    }
+
+    // ends a chain write: rising CS/LOAD edge latches the shift registers
+    fn latch(&mut self) -> Result<(), DriverError> {
+        delay_ns(&mut self.delay, self.timing.cs_hold_ns);
+        self.cs.set_high().map_err(|_| DriverError::Pin)?;
+        delay_ns(&mut self.delay, self.timing.cs_high_ns);
+        Ok(())
+    }
 }
 
-impl<SPI, CS> Connector for SpiConnectorCs<SPI, CS>
+impl<SPI, CS, DELAY> Connector for SpiConnectorCs<SPI, CS, DELAY>
 where
     SPI: Write<u8>,
     CS: OutputPin,
+    DELAY: DelayUs<u32>,
 {
     fn devices(&self) -> usize {
         self.spi_c.devices()
     }
 
+    fn chip(&self, device_addr: usize) -> Chip {
+        self.spi_c.chip(device_addr)
+    }
+
+    fn init(&mut self) -> Result<(), DriverError> {
+        match self.chip(0) {
+            // CS idles high so that the first falling edge starts a clean frame,
+            // a MAX7221 ignores DIN until then
+            Chip::Max7221 => {
+                self.cs.set_high().map_err(|_| DriverError::Pin)?;
+                delay_ns(&mut self.delay, self.timing.cs_high_ns);
+                Ok(())
+            }
+            // LOAD only latches on its rising edge after each chain write
+            Chip::Max7219 => Ok(()),
+        }
+    }
+
     fn write_raw(
         &mut self,
         device_addr: usize,
         register_addr: u8,
         data: u8,
     ) -> Result<(), DriverError> {
-        self.cs.set_low().map_err(|_| DriverError::Pin)?;
+        self.select()?;
         self.spi_c
             .write_raw(device_addr, register_addr, data)
             .map_err(|_| DriverError::Spi)?;
-        self.cs.set_high().map_err(|_| DriverError::Pin)?;
-        Ok(())
+        self.latch()
+    }
+
+    fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        if frame.len() != self.serial_data_max_bytes() {
+            return Err(DriverError::InvalidValue);
+        }
+        self.select()?;
+        self.spi_c.write_chain(frame)?;
+        self.latch()
+    }
+}
+
+impl<SPI, CS, DELAY> LoopbackConnector for SpiConnectorCs<SPI, CS, DELAY>
+where
+    SPI: Write<u8> + Transfer<u8>,
+    CS: OutputPin,
+    DELAY: DelayUs<u32>,
+{
+    fn transfer_raw(&mut self, buffer: &mut [u8]) -> Result<(), DriverError> {
+        self.select()?;
+        self.spi_c.transfer_raw(buffer)?;
+        self.latch()
     }
 }
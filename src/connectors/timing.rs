@@ -0,0 +1,71 @@
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::global::{consts::MAX_SPI_FREQUENCY_HZ, enums::DriverError};
+
+///
+/// Minimum `CS`/`LOAD` timings enforced by a connector with a delay provider.
+///
+/// The datasheet minimums are in the tens of nanoseconds, which most MCUs exceed
+/// on their own. Fast MCUs, long cables or level shifters may need more margin.
+/// Delays are rounded up to whole microseconds.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsTiming {
+    /// Time between `CS` falling and the first clock edge (tCSS, 25 ns min)
+    pub cs_setup_ns: u32,
+    /// Time between the last clock edge and `CS` rising (tCSH, 0 ns min)
+    pub cs_hold_ns: u32,
+    /// Time `CS` stays high between two chain writes (tCSW, 50 ns min)
+    pub cs_high_ns: u32,
+}
+
+impl Default for CsTiming {
+    fn default() -> Self {
+        CsTiming::DATASHEET
+    }
+}
+
+impl CsTiming {
+    /// The minimum timings of the MAX7219/MAX7221 datasheet
+    pub const DATASHEET: CsTiming = CsTiming {
+        cs_setup_ns: 25,
+        cs_hold_ns: 0,
+        cs_high_ns: 50,
+    };
+}
+
+///
+/// A delay provider that does not wait, used when no timing is enforced.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDelay;
+
+impl DelayUs<u32> for NoDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+///
+/// Waits at least `ns` nanoseconds, rounded up to whole microseconds.
+///
+pub(crate) fn delay_ns<DELAY: DelayUs<u32>>(delay: &mut DELAY, ns: u32) {
+    if ns > 0 {
+        delay.delay_us(ns.div_ceil(1000));
+    }
+}
+
+///
+/// Checks an SPI clock frequency against the 10 MHz maximum of the chips.
+///
+/// embedded-hal does not expose the bus frequency, so pass the frequency the
+/// HAL was configured with, e.g. `Max7219Builder::spi_frequency(10u32.MHz().to_Hz())`.
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if `hz` is above `MAX_SPI_FREQUENCY_HZ`
+///
+pub fn check_spi_frequency(hz: u32) -> Result<(), DriverError> {
+    if hz > MAX_SPI_FREQUENCY_HZ {
+        return Err(DriverError::InvalidValue);
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::global::enums::{ DriverError, RegisterAddr};
+use crate::global::enums::{Chip, DriverError, RegisterAddr};
 
 /// Describes the interface used to connect to the MX7219
 pub trait Connector {
@@ -9,6 +9,24 @@ pub trait Connector {
     ///
     fn devices(&self) -> usize;
 
+    ///
+    /// Get the chip variant of the `device_addr` display.
+    ///
+    fn chip(&self, _device_addr: usize) -> Chip {
+        Chip::Max7219
+    }
+
+    ///
+    /// Prepares the bus before the first write, e.g. by driving `CS` to its idle level.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error controlling the bus
+    ///
+    fn init(&mut self) -> Result<(), DriverError> {
+        Ok(())
+    }
+
     ///
     /// Get the writes data max bytes.
     ///
@@ -33,6 +51,27 @@ pub trait Connector {
         self.write_raw(device_addr, register_address as u8, data)
     }
 
+    ///
+    /// Writes one chain frame of `devices() * 2` bytes, `[register_addr, data]` per
+    /// device starting with device 0, and latches it with a single `CS`/`LOAD` pulse.
+    ///
+    /// The default implementation writes each device separately with `write_raw`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `frame` is not `devices() * 2` bytes long
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        if frame.len() != self.serial_data_max_bytes() {
+            return Err(DriverError::InvalidValue);
+        }
+        for (device_addr, pair) in frame.chunks_exact(2).enumerate() {
+            self.write_raw(device_addr, pair[0], pair[1])?;
+        }
+        Ok(())
+    }
+
     ///
     /// Writes data to given register address
     ///
@@ -53,3 +92,20 @@ pub trait Connector {
         data: u8,
     ) -> Result<(), DriverError>;
 }
+
+/// Describes a connector that also reads the `DOUT` of the last device of the chain,
+/// wired back to MISO
+pub trait LoopbackConnector: Connector {
+    ///
+    /// Shifts `buffer` into the chain with a full-duplex transfer, replacing it with
+    /// the bits shifted out of the last device, then pulses `CS`/`LOAD`.
+    ///
+    /// * `NOTE` - whatever ends up in the shift registers is latched, so callers
+    ///   must make sure the last `devices() * 2` bytes shifted in are no-ops.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    fn transfer_raw(&mut self, buffer: &mut [u8]) -> Result<(), DriverError>;
+}
@@ -0,0 +1,280 @@
+//!
+//! Scripted self-test of a chain, to check module order, orientation and dead LEDs.
+//!
+//! The test lights one device at a time, so a technician can follow the chain
+//! from device 0 (the last one) on:
+//!
+//! 1. `Step::DisplayTest` - every LED of the device through the display test register
+//! 2. `Step::Row` - each digit register of the device in turn, a row on a matrix
+//! 3. `Step::Segment` - each segment bit in every digit, a column on a matrix
+//! 4. `Step::Identify` - every device shows its `device_addr`, with a dot in the top
+//!    left corner of matrix modules to check the orientation. Matrix modules fit
+//!    addresses up to 99, seven segment displays up to 99999.
+//!
+//! The displays are set to no decode, all 8 digits scanned and powered on for the
+//! test, and are left cleared when it ends, so they must be reconfigured afterwards.
+//!
+
+use embedded_hal::blocking::delay::DelayMs;
+
+use crate::connectors::traits::Connector;
+use crate::font::{Digits3x5, Font5x7};
+use crate::global::{
+    consts::MAX_DIGITS,
+    enums::{DecodeMode, DisplayTest, DriverError, RegisterAddr, ScanLimit},
+};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+/// The kind of modules in the chain, which changes how devices identify themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// 8x8 LED matrix modules
+    Matrix,
+    /// 8 digit seven segment modules
+    SevenSegment,
+}
+
+/// A step of the self-test, as currently shown on the displays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Every LED of `device_addr` is lit through the display test register
+    DisplayTest { device_addr: usize },
+    /// Every LED of digit register `digit` (0 = Digit0) of `device_addr` is lit
+    Row { device_addr: usize, digit: u8 },
+    /// Segment `bit` (7 = DP) is lit in every digit of `device_addr`
+    Segment { device_addr: usize, bit: u8 },
+    /// Every device shows its own `device_addr` in decimal
+    Identify,
+}
+
+///
+/// Runs the self-test one step at a time.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::delay::DelayMs;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::diagnostics::{Diagnostics, Layout};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector, D: DelayMs<u32>>(mut max7219: MAX7219<C>, mut delay: D) {
+/// let mut diagnostics = Diagnostics::new(Layout::Matrix);
+/// diagnostics.run(&mut max7219, &mut delay, 500).unwrap();
+/// # }
+/// ```
+///
+pub struct Diagnostics {
+    layout: Layout,
+    current: Option<Step>,
+    finished: bool,
+}
+
+impl Diagnostics {
+    ///
+    /// Creates a self-test for the given module layout, starting at the first step.
+    ///
+    pub fn new(layout: Layout) -> Self {
+        Diagnostics {
+            layout,
+            current: None,
+            finished: false,
+        }
+    }
+
+    ///
+    /// Shows the next step of the self-test.
+    ///
+    /// Returns the step now shown, or `None` once the test is over and the
+    /// displays have been cleared.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the chain is too long for the devices
+    ///   to show their `device_addr`
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn step<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<Option<Step>, DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        if self.finished {
+            return Ok(None);
+        }
+        let devices = max7219.devices();
+        if self.current.is_none() {
+            prepare(max7219)?;
+        }
+        let next = match self.current {
+            _ if devices == 0 => None,
+            None => Some(Step::DisplayTest { device_addr: 0 }),
+            Some(Step::DisplayTest { device_addr }) if device_addr + 1 < devices => {
+                Some(Step::DisplayTest {
+                    device_addr: device_addr + 1,
+                })
+            }
+            Some(Step::DisplayTest { .. }) => Some(Step::Row {
+                device_addr: 0,
+                digit: 0,
+            }),
+            Some(Step::Row { device_addr, digit }) if (digit as usize) + 1 < MAX_DIGITS => {
+                Some(Step::Row {
+                    device_addr,
+                    digit: digit + 1,
+                })
+            }
+            Some(Step::Row { device_addr, .. }) if device_addr + 1 < devices => Some(Step::Row {
+                device_addr: device_addr + 1,
+                digit: 0,
+            }),
+            Some(Step::Row { .. }) => Some(Step::Segment {
+                device_addr: 0,
+                bit: 0,
+            }),
+            Some(Step::Segment { device_addr, bit }) if bit < 7 => Some(Step::Segment {
+                device_addr,
+                bit: bit + 1,
+            }),
+            Some(Step::Segment { device_addr, .. }) if device_addr + 1 < devices => {
+                Some(Step::Segment {
+                    device_addr: device_addr + 1,
+                    bit: 0,
+                })
+            }
+            Some(Step::Segment { .. }) => Some(Step::Identify),
+            Some(Step::Identify) => None,
+        };
+
+        match next {
+            Some(step) => self.show(max7219, step)?,
+            None => {
+                max7219.clear_display_all()?;
+                self.finished = true;
+            }
+        }
+        self.current = next;
+        Ok(next)
+    }
+
+    ///
+    /// Runs every remaining step, showing each one for `step_ms` milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn run<CONNECTOR, DELAY, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+        delay: &mut DELAY,
+        step_ms: u32,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+        DELAY: DelayMs<u32>,
+    {
+        while self.step(max7219)?.is_some() {
+            delay.delay_ms(step_ms);
+        }
+        Ok(())
+    }
+
+    fn show<CONNECTOR, const DEVICES: usize>(
+        &self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+        step: Step,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        match step {
+            Step::DisplayTest { device_addr } => {
+                max7219.set_display_test_mode_all(DisplayTest::NormalOperationMode)?;
+                max7219.set_display_test_mode(device_addr, DisplayTest::DisplayTestMode)
+            }
+            Step::Row { device_addr, digit } => {
+                if digit == 0 {
+                    max7219.set_display_test_mode_all(DisplayTest::NormalOperationMode)?;
+                    max7219.clear_display_all()?;
+                } else {
+                    max7219.write_raw(device_addr, RegisterAddr::digit(digit - 1)?, 0x00)?;
+                }
+                max7219.write_raw(device_addr, RegisterAddr::digit(digit)?, 0xFF)
+            }
+            Step::Segment { device_addr, bit } => {
+                if bit == 0 {
+                    max7219.clear_display_all()?;
+                }
+                max7219.write_raw_all(device_addr, &[1 << bit; MAX_DIGITS])
+            }
+            Step::Identify => {
+                max7219.clear_display_all()?;
+                for device_addr in 0..max7219.devices() {
+                    self.identify(max7219, device_addr)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn identify<CONNECTOR, const DEVICES: usize>(
+        &self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+        device_addr: usize,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        match self.layout {
+            Layout::Matrix => {
+                let mut frame = Framebuffer::new(1, 1)?;
+                let mut label = [0; 2];
+                match decimal(device_addr, &mut label)? {
+                    label if label.len() == 1 => frame.draw_text(2, 1, label, &Font5x7),
+                    // two 3x5 digits still fit next to the orientation marker
+                    label => frame.draw_text(1, 2, label, &Digits3x5),
+                };
+                frame.set_pixel(0, 0, true); // orientation marker
+                max7219.write_raw_all(device_addr, frame.device_rows(0))
+            }
+            Layout::SevenSegment => {
+                let mut label = *b"dEv     ";
+                decimal(device_addr, &mut label[3..])?;
+                max7219.write_str(device_addr, &label, 0)
+            }
+        }
+    }
+}
+
+// puts every display in a known state for the test
+fn prepare<CONNECTOR, const DEVICES: usize>(
+    max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+) -> Result<(), DriverError>
+where
+    CONNECTOR: Connector,
+{
+    max7219.set_display_test_mode_all(DisplayTest::NormalOperationMode)?;
+    for device_addr in 0..max7219.devices() {
+        max7219.set_decode_mode(device_addr, DecodeMode::NoDecode)?;
+        max7219.set_scan_limit(device_addr, ScanLimit::Display0To7)?;
+    }
+    max7219.clear_display_all()?;
+    max7219.power_on()
+}
+
+// writes `value` in decimal at the end of `buffer`, failing if it does not fit
+fn decimal(mut value: usize, buffer: &mut [u8]) -> Result<&str, DriverError> {
+    let mut start = buffer.len();
+    loop {
+        start = start.checked_sub(1).ok_or(DriverError::InvalidValue)?;
+        buffer[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    core::str::from_utf8(&buffer[start..]).map_err(|_| DriverError::InvalidValue)
+}
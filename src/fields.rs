@@ -0,0 +1,321 @@
+//!
+//! Independent fields on seven segment displays, e.g. the temperature on the left
+//! four digits of a module and the humidity on the right four.
+//!
+
+use core::fmt::{self, Write};
+use core::ops::Range;
+
+use crate::attributes::{Attribute, AttributedText};
+use crate::connectors::traits::Connector;
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::DriverError,
+};
+use crate::glyphs::SevenSegment;
+use crate::layout::Align;
+use crate::text::SegmentBuffer;
+use crate::MAX7219;
+
+/// Maximum number of fields a `SegmentFields` holds
+pub const MAX_FIELDS: usize = 16;
+
+/// Refers to a field of a `SegmentFields`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldId(usize);
+
+#[derive(Debug, Clone, Default)]
+struct Field {
+    device_addr: usize,
+    positions: Range<usize>,
+    align: Align,
+}
+
+///
+/// Seven segment displays split into fields that are written, cleared, blinked
+/// and formatted independently.
+///
+/// The content is kept in an `AttributedText`, so `tick` only rewrites the digit
+/// registers whose visible pattern changed and writing one field never touches
+/// the digits of another. Digits outside every field are never written, and
+/// `NoDecode` is only set on the displays covered by a field. Text is rendered with the `SevenSegment` glyph table, a
+/// `.` being folded into the preceding digit.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::delay::DelayMs;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::fields::SegmentFields;
+/// # use max7219_driver::layout::Align;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, delay: &mut impl DelayMs<u32>) {
+/// let mut fields = SegmentFields::new();
+/// let temperature = fields.add_field(0, 0..4, Align::Right).unwrap();
+/// let humidity = fields.add_field(0, 4..8, Align::Right).unwrap();
+/// fields.write_fmt(temperature, format_args!("{:.1}C", 21.5)).unwrap();
+/// fields.write_fmt(humidity, format_args!("{}H", 40)).unwrap();
+/// fields.set_blink(humidity, true).unwrap();
+/// loop {
+///     fields.tick(max7219).unwrap();
+///     delay.delay_ms(10_u32);
+/// }
+/// # }
+/// ```
+///
+pub struct SegmentFields<const DEVICES: usize = MAX_DISPLAYS> {
+    text: AttributedText<DEVICES>,
+    fields: [Field; MAX_FIELDS],
+    count: usize,
+}
+
+impl<const DEVICES: usize> Default for SegmentFields<DEVICES> {
+    fn default() -> Self {
+        SegmentFields::with_capacity()
+    }
+}
+
+impl SegmentFields {
+    ///
+    /// Creates up to `MAX_DISPLAYS` blank displays without fields.
+    ///
+    pub fn new() -> Self {
+        SegmentFields::with_capacity()
+    }
+}
+
+impl<const DEVICES: usize> SegmentFields<DEVICES> {
+    ///
+    /// Creates up to `DEVICES` blank displays without fields.
+    ///
+    pub fn with_capacity() -> Self {
+        SegmentFields {
+            text: AttributedText::with_capacity(),
+            fields: Default::default(),
+            count: 0,
+        }
+    }
+
+    ///
+    /// Adds a field, blank until written.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `positions` - character positions covered by the field, counted from the left
+    /// * `align` - where text shorter than the field is placed within it
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the positions are empty, out of range or
+    ///   overlap another field, or there are already `MAX_FIELDS` fields
+    ///
+    pub fn add_field(
+        &mut self,
+        device_addr: usize,
+        positions: Range<usize>,
+        align: Align,
+    ) -> Result<FieldId, DriverError> {
+        let overlaps = self.fields[..self.count].iter().any(|field| {
+            field.device_addr == device_addr
+                && field.positions.start < positions.end
+                && positions.start < field.positions.end
+        });
+        if device_addr >= DEVICES
+            || positions.is_empty()
+            || positions.end > MAX_DIGITS
+            || overlaps
+            || self.count == MAX_FIELDS
+        {
+            return Err(DriverError::InvalidValue);
+        }
+        self.fields[self.count] = Field {
+            device_addr,
+            positions,
+            align,
+        };
+        self.count += 1;
+        let id = FieldId(self.count - 1);
+        self.clear(id)?;
+        Ok(id)
+    }
+
+    ///
+    /// Get the number of digits of a field, 0 if it does not exist.
+    ///
+    pub fn width(&self, id: FieldId) -> usize {
+        self.field(id).map_or(0, |field| field.positions.len())
+    }
+
+    ///
+    /// Sets the content of a field, blanking the digits the text does not cover.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the text does not fit into the field,
+    ///   or the field does not exist
+    ///
+    pub fn write_str(&mut self, id: FieldId, text: &str) -> Result<(), DriverError> {
+        self.write_fmt(id, format_args!("{}", text))
+    }
+
+    ///
+    /// Sets the content of a field from formatted text, e.g. `format_args!("{:.1}", value)`,
+    /// blanking the digits the text does not cover.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the text does not fit into the field,
+    ///   or the field does not exist
+    ///
+    pub fn write_fmt(&mut self, id: FieldId, args: fmt::Arguments) -> Result<(), DriverError> {
+        let width = self.field(id)?.positions.len();
+        let mut sink = FieldSink {
+            buffer: SegmentBuffer::default(),
+            width,
+        };
+        sink.write_fmt(args)
+            .map_err(|_| DriverError::InvalidValue)?;
+        let padding = width - sink.buffer.len;
+        let offset = match self.field(id)?.align {
+            Align::Left => 0,
+            Align::Center => padding / 2,
+            Align::Right => padding,
+        };
+        let mut segments = [0; MAX_DIGITS];
+        segments[offset..offset + sink.buffer.len]
+            .copy_from_slice(&sink.buffer.segments[..sink.buffer.len]);
+        self.set_segments(id, &segments[..width])
+    }
+
+    ///
+    /// Sets the segment patterns of a field, in `DP A B C D E F G` order from left to right.
+    /// Digits without a pattern are blanked.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if there are more patterns than digits,
+    ///   or the field does not exist
+    ///
+    pub fn set_segments(&mut self, id: FieldId, segments: &[u8]) -> Result<(), DriverError> {
+        let field = self.field(id)?;
+        if segments.len() > field.positions.len() {
+            return Err(DriverError::InvalidValue);
+        }
+        for (index, position) in field.positions.enumerate() {
+            let pattern = segments.get(index).copied().unwrap_or(0);
+            self.text
+                .set_segments(field.device_addr, position, pattern)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Blanks a field.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the field does not exist
+    ///
+    pub fn clear(&mut self, id: FieldId) -> Result<(), DriverError> {
+        self.set_segments(id, &[])
+    }
+
+    ///
+    /// Sets the attribute of every digit of a field.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the field does not exist
+    ///
+    pub fn set_attribute(&mut self, id: FieldId, attribute: Attribute) -> Result<(), DriverError> {
+        let field = self.field(id)?;
+        for position in field.positions {
+            self.text
+                .set_attribute(field.device_addr, position, attribute)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Makes a field blink or stop blinking, e.g. while it is being edited.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the field does not exist
+    ///
+    pub fn set_blink(&mut self, id: FieldId, blink: bool) -> Result<(), DriverError> {
+        let attribute = if blink {
+            Attribute::BLINK
+        } else {
+            Attribute::NONE
+        };
+        self.set_attribute(id, attribute)
+    }
+
+    ///
+    /// Get the underlying content, e.g. to change the blink rate.
+    ///
+    pub fn text_mut(&mut self) -> &mut AttributedText<DEVICES> {
+        &mut self.text
+    }
+
+    ///
+    /// Writes every digit covered by a field, regardless of what was written before.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if a field is on a display past the end
+    ///   of the chain
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn render<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        self.text.render(max7219)
+    }
+
+    ///
+    /// Advances one frame and rewrites the digits whose visible pattern changed.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if a field is on a display past the end
+    ///   of the chain
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn tick<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        self.text.tick(max7219)
+    }
+
+    // ids of another instance may refer to fields that do not exist here
+    fn field(&self, id: FieldId) -> Result<Field, DriverError> {
+        self.fields[..self.count]
+            .get(id.0)
+            .cloned()
+            .ok_or(DriverError::InvalidValue)
+    }
+}
+
+// collects formatted text for a field `width` digits wide
+struct FieldSink {
+    buffer: SegmentBuffer,
+    width: usize,
+}
+
+impl Write for FieldSink {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars()
+            .try_for_each(|c| self.buffer.push(c, &SevenSegment, self.width))
+    }
+}
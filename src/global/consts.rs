@@ -6,3 +6,6 @@ pub const MAX_SERIAL_DATA_BYTES: usize = MAX_DISPLAYS * 2;
 
 /// Digits per display
 pub const MAX_DIGITS: usize = 8;
+
+/// Maximum SPI clock frequency supported by the MAX7219 and MAX7221.
+pub const MAX_SPI_FREQUENCY_HZ: u32 = 10_000_000;
@@ -3,18 +3,110 @@
 /// during communication with the MAX7219 chip over SPI
 /// or controlling the chip select pin.
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DriverError {
     /// An error occurred when working with SPI
     Spi,
     /// An error occurred when working with a PIN
     Pin,
+    /// The shared SPI bus is held by another driver
+    Bus,
+    /// The data shifted through the chain did not come back as expected
+    Chain,
+    /// A value is out of range for the register it was meant for
+    InvalidValue,
+}
+
+///
+/// Implements the conversions shared by all register value enums:
+/// `ALL` listing every variant in register order, `TryFrom<u8>` for
+/// validating raw input and `From<Enum> for u8` for the way back.
+///
+macro_rules! register_value {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl $name {
+            /// Every value of this register, in ascending register order.
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            /// Iterates over every value of this register, in ascending register order.
+            pub fn iter() -> impl Iterator<Item = $name> {
+                Self::ALL.iter().copied()
+            }
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = DriverError;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                $(
+                    if value == $name::$variant as u8 {
+                        return Ok($name::$variant);
+                    }
+                )+
+                Err(DriverError::InvalidValue)
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> u8 {
+                value as u8
+            }
+        }
+    };
+}
+
+///
+/// The chip variant driven through the connector.
+///
+/// The MAX7221 is pin and register compatible with the MAX7219 but differs in
+/// two ways relevant to the driver:
+///
+/// * its `CS` is SPI compliant: `DIN` is only shifted in while `CS` is low, so
+///   other traffic on the bus is ignored, whereas the MAX7219 shifts in every
+///   clock edge and only uses `LOAD` to latch the last 16 bits per device.
+/// * its intensity register steps the duty cycle in sixteenths (1/16 to 15/16)
+///   instead of the odd thirty-seconds of the MAX7219 (1/32 to 31/32).
+///
+/// The connectors with a software `CS` drive it low for every chain write and high
+/// once the whole chain has been clocked in, which latches both chips. For a MAX7221,
+/// `CS` is also set high on init so that it idles high. Intensity compensation uses
+/// the duty cycle table of the chip.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Chip {
+    /// MAX7219, `LOAD` only latches the shift register
+    #[default]
+    Max7219,
+    /// MAX7221, SPI compliant `CS`
+    Max7221,
+}
+
+impl Chip {
+    ///
+    /// Whether the chip ignores the serial input while `CS` is high, so that
+    /// other devices can use the same bus without shifting data into the chain.
+    ///
+    pub fn has_spi_compliant_cs(self) -> bool {
+        matches!(self, Chip::Max7221)
+    }
+
+    ///
+    /// Get the PWM duty cycle numerator of an intensity on this chip, over 32.
+    ///
+    pub fn duty_cycle_32(self, intensity: Intensity) -> u8 {
+        let level = intensity as u8;
+        match self {
+            Chip::Max7219 => level * 2 + 1,
+            // (level + 1) / 16, the two highest levels both being 15/16
+            Chip::Max7221 => (level + 1).min(15) * 2,
+        }
+    }
 }
 
 /// the register address map of the MAX7219.
 /// #[repr(u8)] 每个变体占用一个字节内存
 #[repr(u8)]
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RegisterAddr {
     NoOp = 0x00,
     Digit0 = 0x01,
@@ -34,6 +126,7 @@ pub enum RegisterAddr {
 
 /// the MAX7219 power modes.
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Shutdown {
     ShutdownMode = 0x00,
     NormalOperation = 0x01,
@@ -41,6 +134,7 @@ pub enum Shutdown {
 
 /// the MAX7219 decode modes for BCD encoded input.
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DecodeMode {
     NoDecode = 0x00,
     CodeBDigit0 = 0x01,
@@ -50,6 +144,7 @@ pub enum DecodeMode {
 
 /// the MAX7219 supported LED intensity values.
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Intensity {
     Min = 0x00,
     Ratio3_32 = 0x01,
@@ -71,6 +166,7 @@ pub enum Intensity {
 
 /// the MAX7219 display scan limits
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScanLimit {
     Display0Only = 0x00,
     Display0And1 = 0x01,
@@ -84,7 +180,169 @@ pub enum ScanLimit {
 
 /// the MAX7219 display test modes
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayTest {
     NormalOperationMode = 0x00,
     DisplayTestMode = 0x01,
 }
+
+register_value!(RegisterAddr {
+    NoOp,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    DecodeMode,
+    Intensity,
+    ScanLimit,
+    Shutdown,
+    DisplayTest,
+});
+register_value!(Shutdown {
+    ShutdownMode,
+    NormalOperation
+});
+register_value!(DecodeMode {
+    NoDecode,
+    CodeBDigit0,
+    CodeBDigits3_0,
+    CodeBDigits7_0
+});
+register_value!(Intensity {
+    Min,
+    Ratio3_32,
+    Ratio5_32,
+    Ratio7_32,
+    Ratio9_32,
+    Ratio11_32,
+    Ratio13_32,
+    Ratio15_32,
+    Ratio17_32,
+    Ratio19_32,
+    Ratio21_32,
+    Ratio23_32,
+    Ratio25_32,
+    Ratio27_32,
+    Ratio29_32,
+    Max,
+});
+register_value!(ScanLimit {
+    Display0Only,
+    Display0And1,
+    Display0To2,
+    Display0To3,
+    Display0To4,
+    Display0To5,
+    Display0To6,
+    Display0To7,
+});
+register_value!(DisplayTest {
+    NormalOperationMode,
+    DisplayTestMode
+});
+
+impl RegisterAddr {
+    ///
+    /// Get the digit register for the given digit index.
+    ///
+    /// # Arguments
+    ///
+    /// * `digit` - digit index in the range 0..=7
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `digit` is out of range
+    ///
+    pub fn digit(digit: u8) -> Result<Self, DriverError> {
+        if digit > 7 {
+            return Err(DriverError::InvalidValue);
+        }
+        RegisterAddr::try_from(RegisterAddr::Digit0 as u8 + digit)
+    }
+}
+
+impl Intensity {
+    ///
+    /// Get the intensity closest to the given percentage of full brightness.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - brightness in the range 0..=100
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `percent` is above 100
+    ///
+    pub fn from_percent(percent: u8) -> Result<Self, DriverError> {
+        if percent > 100 {
+            return Err(DriverError::InvalidValue);
+        }
+        let level = (percent as u16 * Intensity::Max as u16 + 50) / 100;
+        Intensity::try_from(level as u8)
+    }
+
+    ///
+    /// Get the brightness of this intensity in percent, the inverse of `from_percent`.
+    ///
+    pub fn percent(self) -> u8 {
+        ((self as u16 * 100 + Intensity::Max as u16 / 2) / Intensity::Max as u16) as u8
+    }
+
+    ///
+    /// Get the intensity that makes a display scanning `scan_limit` digits appear
+    /// as bright as one scanning all 8 digits at this intensity.
+    ///
+    /// Each digit is lit for `1 / digits` of the scan period, so the duty cycle of
+    /// `chip` is scaled by `digits / 8` and rounded to the closest register value,
+    /// ties going to the dimmer one. Low intensities on short scan limits cannot be
+    /// fully compensated and clamp to `Min`.
+    ///
+    pub fn compensated_for(self, scan_limit: ScanLimit, chip: Chip) -> Intensity {
+        // target duty * 8, compared against the duty of every level * 8
+        let target = chip.duty_cycle_32(self) as i16 * scan_limit.digit_count() as i16;
+        let error =
+            |intensity: &Intensity| (chip.duty_cycle_32(*intensity) as i16 * 8 - target).abs();
+        if error(&self) == 0 {
+            // nothing to compensate, keep the level even if another one has the same duty
+            return self;
+        }
+        Intensity::iter().fold(Intensity::Min, |best, intensity| {
+            if error(&intensity) < error(&best) {
+                intensity
+            } else {
+                best
+            }
+        })
+    }
+}
+
+impl ScanLimit {
+    ///
+    /// Get the scan limit that displays exactly `count` digits.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - number of scanned digits in the range 1..=8
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `count` is out of range
+    ///
+    pub fn from_digit_count(count: u8) -> Result<Self, DriverError> {
+        if count == 0 {
+            return Err(DriverError::InvalidValue);
+        }
+        ScanLimit::try_from(count - 1)
+    }
+
+    ///
+    /// Get the number of digits scanned with this scan limit.
+    ///
+    pub fn digit_count(self) -> u8 {
+        self as u8 + 1
+    }
+}
@@ -0,0 +1,248 @@
+//!
+//! Glyph tables translating characters into segment patterns.
+//!
+//! Seven segment patterns are bytes in the `DP A B C D E F G` bit order of the
+//! MAX7219 no-decode mode. Custom tables implement `GlyphTable`, usually falling
+//! back to `SevenSegment` for the characters they do not override:
+//!
+//! ```
+//! use max7219_driver::glyphs::{GlyphTable, SevenSegment};
+//!
+//! struct MyGlyphs;
+//!
+//! impl GlyphTable for MyGlyphs {
+//!     type Glyph = u8;
+//!
+//!     fn glyph(&self, c: char) -> Option<u8> {
+//!         match c {
+//!             'M' => Some(0b0111_0110), // "n" with a top bar
+//!             _ => SevenSegment.glyph(c),
+//!         }
+//!     }
+//!
+//!     fn unknown(&self) -> u8 {
+//!         SevenSegment.unknown()
+//!     }
+//! }
+//! ```
+//!
+//! Fourteen segment patterns are `u16`s in the bit order of the `segment14` module.
+//! Each glyph spans two digit registers, written with `MAX7219::write_str_14seg`
+//! or, for a custom `Glyph = u16` table, `MAX7219::write_str_wide_with`.
+//!
+
+/// Segment bits in the `DP A B C D E F G` order of the no-decode mode
+pub mod segment {
+    pub const DP: u8 = 0b1000_0000;
+    pub const A: u8 = 0b0100_0000;
+    pub const B: u8 = 0b0010_0000;
+    pub const C: u8 = 0b0001_0000;
+    pub const D: u8 = 0b0000_1000;
+    pub const E: u8 = 0b0000_0100;
+    pub const F: u8 = 0b0000_0010;
+    pub const G: u8 = 0b0000_0001;
+}
+
+/// Segment bits of a fourteen segment glyph. The low byte holds the outer segments
+/// and the middle bar, the high byte the inner strokes and the decimal point.
+pub mod segment14 {
+    /// top
+    pub const A: u16 = 1 << 0;
+    /// upper right
+    pub const B: u16 = 1 << 1;
+    /// lower right
+    pub const C: u16 = 1 << 2;
+    /// bottom
+    pub const D: u16 = 1 << 3;
+    /// lower left
+    pub const E: u16 = 1 << 4;
+    /// upper left
+    pub const F: u16 = 1 << 5;
+    /// middle bar, left half
+    pub const G1: u16 = 1 << 6;
+    /// middle bar, right half
+    pub const G2: u16 = 1 << 7;
+    /// diagonal from the top left corner to the centre
+    pub const H: u16 = 1 << 8;
+    /// vertical from the top to the centre
+    pub const I: u16 = 1 << 9;
+    /// diagonal from the top right corner to the centre
+    pub const J: u16 = 1 << 10;
+    /// diagonal from the bottom left corner to the centre
+    pub const K: u16 = 1 << 11;
+    /// vertical from the bottom to the centre
+    pub const L: u16 = 1 << 12;
+    /// diagonal from the bottom right corner to the centre
+    pub const M: u16 = 1 << 13;
+    /// decimal point
+    pub const DP: u16 = 1 << 14;
+}
+
+///
+/// Describes how characters are rendered on a segment display.
+///
+pub trait GlyphTable {
+    /// The segment pattern of one character, `u8` for seven segment displays
+    type Glyph: Copy;
+
+    ///
+    /// Get the segment pattern for `c`, or `None` if this table cannot render it.
+    ///
+    fn glyph(&self, c: char) -> Option<Self::Glyph>;
+
+    ///
+    /// Get the segment pattern rendered for characters without a glyph.
+    ///
+    fn unknown(&self) -> Self::Glyph;
+
+    ///
+    /// Get the segment pattern for `c`, falling back to `unknown`.
+    ///
+    fn encode(&self, c: char) -> Self::Glyph {
+        self.glyph(c).unwrap_or_else(|| self.unknown())
+    }
+}
+
+///
+/// The default seven segment table.
+///
+/// Covers digits, all latin letters (K, M, V, W, X and Z as approximations),
+/// and `space . - _ = ' " [ ] ( ) / ° ?`. Bytes passed to `MAX7219::write_str`
+/// are read as Latin-1, so `°` is written as `b'\xB0'`.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SevenSegment;
+
+impl GlyphTable for SevenSegment {
+    type Glyph = u8;
+
+    fn glyph(&self, c: char) -> Option<u8> {
+        let result = match c {
+            ' ' => 0b0000_0000, // "blank"
+            '.' => 0b1000_0000,
+            '-' => 0b0000_0001, // -
+            '_' => 0b0000_1000, // _
+            '=' => 0b0000_1001,
+            '\'' => 0b0000_0010,
+            '"' => 0b0010_0010,
+            '[' | '(' => 0b0100_1110,
+            ']' | ')' => 0b0111_1000,
+            '/' => 0b0010_0101,
+            '°' => 0b0110_0011,
+            '?' => 0b1110_0101,
+            '0' => 0b0111_1110,
+            '1' => 0b0011_0000,
+            '2' => 0b0110_1101,
+            '3' => 0b0111_1001,
+            '4' => 0b0011_0011,
+            '5' => 0b0101_1011,
+            '6' => 0b0101_1111,
+            '7' => 0b0111_0000,
+            '8' => 0b0111_1111,
+            '9' => 0b0111_1011,
+            'a' | 'A' => 0b0111_0111,
+            'b' | 'B' => 0b0001_1111,
+            'c' | 'C' => 0b0100_1110,
+            'd' | 'D' => 0b0011_1101,
+            'e' | 'E' => 0b0100_1111,
+            'f' | 'F' => 0b0100_0111,
+            'g' | 'G' => 0b0101_1110,
+            'h' | 'H' => 0b0011_0111,
+            'i' | 'I' => 0b0011_0000,
+            'j' | 'J' => 0b0011_1100,
+            'k' | 'K' => 0b0101_0111, // approximation, H without the upper right stroke, with a top bar
+            'l' | 'L' => 0b0000_1110,
+            'm' | 'M' => 0b0101_0100, // approximation, top bar over the two lower strokes
+            'n' | 'N' => 0b0001_0101,
+            'o' | 'O' => 0b0111_1110,
+            'p' | 'P' => 0b0110_0111,
+            'q' | 'Q' => 0b0111_0011,
+            'r' | 'R' => 0b0000_0101,
+            's' | 'S' => 0b0101_1011,
+            't' | 'T' => 0b0000_1111,
+            'u' | 'U' => 0b0011_1110,
+            'v' | 'V' => 0b0001_1100, // approximation, lower case u
+            'w' | 'W' => 0b0010_1010, // approximation, bottom bar under the two upper strokes
+            'x' | 'X' => 0b0011_0111, // approximation, same as H
+            'y' | 'Y' => 0b0011_1011,
+            'z' | 'Z' => 0b0110_1101, // approximation, same as 2
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn unknown(&self) -> u8 {
+        0b1110_0101 // ?
+    }
+}
+
+///
+/// A basic fourteen segment table.
+///
+/// Covers digits, latin letters (lower case rendered as upper case) and
+/// `space . - _ = + * / \ ?`.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FourteenSegment;
+
+impl GlyphTable for FourteenSegment {
+    type Glyph = u16;
+
+    fn glyph(&self, c: char) -> Option<u16> {
+        use segment14::*;
+        let result = match c.to_ascii_uppercase() {
+            ' ' => 0,
+            '.' => DP,
+            '-' => G1 | G2,
+            '_' => D,
+            '=' => D | G1 | G2,
+            '+' => G1 | G2 | I | L,
+            '*' => G1 | G2 | H | I | J | K | L | M,
+            '/' => J | K,
+            '\\' => H | M,
+            '?' => A | B | G2 | L,
+            '0' => A | B | C | D | E | F | J | K,
+            '1' => B | C | J,
+            '2' => A | B | D | E | G1 | G2,
+            '3' => A | B | C | D | G2,
+            '4' => B | C | F | G1 | G2,
+            '5' => A | C | D | F | G1 | G2,
+            '6' => A | C | D | E | F | G1 | G2,
+            '7' => A | B | C,
+            '8' => A | B | C | D | E | F | G1 | G2,
+            '9' => A | B | C | D | F | G1 | G2,
+            'A' => A | B | C | E | F | G1 | G2,
+            'B' => A | B | C | D | G2 | I | L,
+            'C' => A | D | E | F,
+            'D' => A | B | C | D | I | L,
+            'E' => A | D | E | F | G1 | G2,
+            'F' => A | E | F | G1,
+            'G' => A | C | D | E | F | G2,
+            'H' => B | C | E | F | G1 | G2,
+            'I' => A | D | I | L,
+            'J' => B | C | D | E,
+            'K' => E | F | G1 | J | M,
+            'L' => D | E | F,
+            'M' => B | C | E | F | H | J,
+            'N' => B | C | E | F | H | M,
+            'O' => A | B | C | D | E | F,
+            'P' => A | B | E | F | G1 | G2,
+            'Q' => A | B | C | D | E | F | M,
+            'R' => A | B | E | F | G1 | G2 | M,
+            'S' => A | C | D | F | G1 | G2,
+            'T' => A | I | L,
+            'U' => B | C | D | E | F,
+            'V' => E | F | J | K,
+            'W' => B | C | E | F | K | M,
+            'X' => H | J | K | M,
+            'Y' => H | J | L,
+            'Z' => A | D | J | K,
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn unknown(&self) -> u16 {
+        segment14::A | segment14::B | segment14::G2 | segment14::L // ?
+    }
+}
@@ -0,0 +1,333 @@
+//!
+//! Text layout for matrix displays: measuring, aligning, word wrapping and
+//! truncating text within an area of a framebuffer.
+//!
+
+use crate::font::{Font, Font5x7};
+use crate::matrix::{Framebuffer, Rect};
+
+/// Marks text cut short by `TextLayout` when ellipsis truncation is enabled
+pub const ELLIPSIS: &str = "...";
+
+///
+/// The horizontal alignment of text lines within their area.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+///
+/// How a single character is drawn by `TextLayout::draw_styled`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CharStyle {
+    /// The character is left out, keeping its space
+    pub hidden: bool,
+    /// The character cell is lit and the glyph drawn dark
+    pub inverted: bool,
+    /// Vertical offset of the glyph in pixels, negative being up
+    pub offset_y: i32,
+}
+
+///
+/// Lays text out within an area of a framebuffer.
+///
+/// `\n` always starts a new line. With wrapping enabled, lines are also broken
+/// between words, or within a word that is wider than the area on its own.
+/// Lines that do not fit below each other are dropped, and anything running past
+/// the area is clipped.
+///
+/// With ellipsis truncation enabled, a line that is too wide, or the last line
+/// that fits when there is more text, ends in `...` instead.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::font::Font5x7;
+/// # use max7219_driver::layout::{Align, TextLayout};
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>) {
+/// let mut frame = Framebuffer::new(4, 1).unwrap();
+/// let mut layout = TextLayout::new(Font5x7);
+/// layout.set_align(Align::Center);
+/// let area = frame.bounds();
+/// layout.draw(&mut frame, area, "Hello");
+/// max7219.write_frame(&frame).unwrap();
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct TextLayout<FONT = Font5x7> {
+    font: FONT,
+    align: Align,
+    wrap: bool,
+    ellipsis: bool,
+    line_spacing: i32,
+}
+
+impl<FONT: Font> TextLayout<FONT> {
+    ///
+    /// Creates a layout with `font`, aligned left, without wrapping or ellipsis
+    /// and one blank pixel row between lines.
+    ///
+    pub fn new(font: FONT) -> Self {
+        TextLayout {
+            font,
+            align: Align::Left,
+            wrap: false,
+            ellipsis: false,
+            line_spacing: 1,
+        }
+    }
+
+    ///
+    /// Sets the horizontal alignment of the lines.
+    ///
+    pub fn set_align(&mut self, align: Align) {
+        self.align = align;
+    }
+
+    ///
+    /// Enables or disables breaking lines between words.
+    ///
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    ///
+    /// Enables or disables ending truncated text in `...`.
+    ///
+    pub fn set_ellipsis(&mut self, ellipsis: bool) {
+        self.ellipsis = ellipsis;
+    }
+
+    ///
+    /// Sets the number of blank pixel rows between lines.
+    ///
+    pub fn set_line_spacing(&mut self, line_spacing: u8) {
+        self.line_spacing = line_spacing as i32;
+    }
+
+    ///
+    /// Get the width of `text` in pixels, as a single line.
+    ///
+    pub fn measure(&self, text: &str) -> i32 {
+        self.font.text_width(text)
+    }
+
+    ///
+    /// Get the number of lines `text` takes when laid out `width` pixels wide,
+    /// without limiting the height. A trailing `\n` does not start another line,
+    /// and nothing fits into a width that is not positive.
+    ///
+    pub fn line_count(&self, text: &str, width: i32) -> usize {
+        if width <= 0 {
+            return 0;
+        }
+        let mut rest = Some(text);
+        let mut lines = 0;
+        while let Some(text) = rest {
+            rest = self.break_line(text, width).1;
+            lines += 1;
+        }
+        lines
+    }
+
+    ///
+    /// Get the height in pixels of `lines` lines of text.
+    ///
+    pub fn height(&self, lines: usize) -> i32 {
+        match lines {
+            0 => 0,
+            lines => lines as i32 * self.line_height() - self.line_spacing,
+        }
+    }
+
+    ///
+    /// Draws `text` into the `area` of `frame`. Only lit pixels are drawn,
+    /// the background is left as is.
+    ///
+    /// Returns the number of lines drawn.
+    ///
+    pub fn draw<const MODULES: usize>(
+        &self,
+        frame: &mut Framebuffer<MODULES>,
+        area: Rect,
+        text: &str,
+    ) -> usize {
+        self.draw_styled(frame, area, text, |_| CharStyle::default())
+    }
+
+    ///
+    /// Draws `text` into the `area` of `frame` like `draw`, styling each character
+    /// with `style`, called with the index of the character in `text`.
+    /// The ellipsis is not styled.
+    ///
+    /// Returns the number of lines drawn, 0 if the area is empty.
+    ///
+    pub fn draw_styled<S, const MODULES: usize>(
+        &self,
+        frame: &mut Framebuffer<MODULES>,
+        area: Rect,
+        text: &str,
+        style: S,
+    ) -> usize
+    where
+        S: Fn(usize) -> CharStyle,
+    {
+        if area.width <= 0 {
+            return 0;
+        }
+        let max_lines = ((area.height + self.line_spacing) / self.line_height()).max(0) as usize;
+        let mut rest = Some(text);
+        let mut drawn = 0;
+        while let Some(remaining) = rest.filter(|_| drawn < max_lines) {
+            let (line, next) = self.break_line(remaining, area.width);
+            let cut = next.is_some() && drawn + 1 == max_lines;
+            let y = area.y + drawn as i32 * self.line_height();
+            // lines are slices of `text`, so their offset gives the index of their first character
+            let first = text[..line.as_ptr() as usize - text.as_ptr() as usize]
+                .chars()
+                .count();
+            let line_style = |index: usize| style(first + index);
+            if self.ellipsis && (cut || self.measure(line) > area.width) {
+                let spacing = self.font.spacing() as i32;
+                let available = area.width - self.measure(ELLIPSIS) - spacing;
+                let line = self.truncate(line, available);
+                let width = match self.measure(line) {
+                    0 => self.measure(ELLIPSIS),
+                    width => width + spacing + self.measure(ELLIPSIS),
+                };
+                let x = self.aligned_x(area, width);
+                let x = self.draw_clipped(frame, area, x, y, line, line_style);
+                self.draw_clipped(frame, area, x, y, ELLIPSIS, |_| CharStyle::default());
+            } else {
+                let x = self.aligned_x(area, self.measure(line));
+                self.draw_clipped(frame, area, x, y, line, line_style);
+            }
+            rest = next;
+            drawn += 1;
+        }
+        drawn
+    }
+
+    fn line_height(&self) -> i32 {
+        self.font.height() as i32 + self.line_spacing
+    }
+
+    fn aligned_x(&self, area: Rect, width: i32) -> i32 {
+        match self.align {
+            Align::Left => area.x,
+            Align::Center => area.x + (area.width - width) / 2,
+            Align::Right => area.x + area.width - width,
+        }
+    }
+
+    ///
+    /// Splits off the first line of `text`, returning it and the text after it,
+    /// or `None` if it was the last line.
+    ///
+    fn break_line<'t>(&self, text: &'t str, width: i32) -> (&'t str, Option<&'t str>) {
+        let (line, rest) = self.split_line(text, width);
+        // nothing after a trailing line break or wrapped space is no line of its own
+        (line, rest.filter(|rest| !rest.is_empty()))
+    }
+
+    fn split_line<'t>(&self, text: &'t str, width: i32) -> (&'t str, Option<&'t str>) {
+        let spacing = self.font.spacing() as i32;
+        let mut cursor = 0;
+        let mut last_space = None;
+        for (i, c) in text.char_indices() {
+            if c == '\n' {
+                return (&text[..i], Some(&text[i + 1..]));
+            }
+            if !self.wrap {
+                continue;
+            }
+            let glyph_width = self.glyph_width(c);
+            if c == ' ' {
+                last_space = Some(i);
+            } else if cursor > 0 && cursor + glyph_width > width {
+                return match last_space {
+                    Some(space) => (
+                        text[..space].trim_end_matches(' '),
+                        Some(text[space + 1..].trim_start_matches(' ')),
+                    ),
+                    None => (&text[..i], Some(&text[i..])),
+                };
+            }
+            if glyph_width > 0 {
+                cursor += glyph_width + spacing;
+            }
+        }
+        (text, None)
+    }
+
+    /// Get the longest start of `line` that is at most `width` pixels wide
+    fn truncate<'t>(&self, line: &'t str, width: i32) -> &'t str {
+        let mut end = 0;
+        for (i, c) in line.char_indices() {
+            let next = i + c.len_utf8();
+            if self.measure(&line[..next]) > width {
+                break;
+            }
+            end = next;
+        }
+        line[..end].trim_end_matches(' ')
+    }
+
+    fn glyph_width(&self, c: char) -> i32 {
+        self.font.glyph(c).map_or(0, |columns| columns.len() as i32)
+    }
+
+    /// Draws a line clipped to `area`, returning the x position for following text
+    fn draw_clipped<S, const MODULES: usize>(
+        &self,
+        frame: &mut Framebuffer<MODULES>,
+        area: Rect,
+        x: i32,
+        y: i32,
+        text: &str,
+        style: S,
+    ) -> i32
+    where
+        S: Fn(usize) -> CharStyle,
+    {
+        let mut cursor = x;
+        for (index, c) in text.chars().enumerate().filter(|(_, c)| !c.is_control()) {
+            let Some(columns) = self.font.glyph(c) else {
+                continue;
+            };
+            let style = style(index);
+            let cell_width = columns.len() + self.font.spacing() as usize;
+            let drawn_width = match style {
+                CharStyle { hidden: true, .. } => 0,
+                CharStyle { inverted: true, .. } => cell_width,
+                _ => columns.len(),
+            };
+            for dx in 0..drawn_width {
+                let column = columns.get(dx).copied().unwrap_or(0);
+                for dy in 0..self.font.height().min(8) {
+                    let (px, py) = (cursor + dx as i32, y + dy as i32 + style.offset_y);
+                    let lit = column & (1 << dy) != 0;
+                    if !area.contains(px, py) {
+                        continue;
+                    }
+                    if style.inverted {
+                        frame.set_pixel(px, py, !lit);
+                    } else if lit {
+                        frame.set_pixel(px, py, true);
+                    }
+                }
+            }
+            cursor += cell_width as i32;
+        }
+        cursor
+    }
+}
@@ -1,35 +1,94 @@
 #![no_std]
 
+pub mod attributes;
+pub mod builder;
+pub mod canvas;
 pub mod connectors;
+pub mod diagnostics;
+pub mod fields;
+pub mod font;
 pub mod global;
-use connectors::{spi::SpiConnector, spi_cs::SpiConnectorCs, traits::Connector};
+pub mod glyphs;
+pub mod layout;
+pub mod matrix;
+pub mod stream;
+pub mod text;
+pub mod viewport;
+pub mod widgets;
+pub mod wiring;
+use builder::Max7219Builder;
+use connectors::{
+    multi::MultiConnector,
+    shared::{BusLock, SharedSpiConnectorCs},
+    spi::SpiConnector,
+    spi_cs::SpiConnectorCs,
+    timing::CsTiming,
+    traits::{Connector, LoopbackConnector},
+};
+use embedded_hal::blocking::delay::DelayUs;
 use embedded_hal::blocking::spi::Write;
 use embedded_hal::digital::v2::OutputPin;
-use global::{consts::MAX_DIGITS, enums::*};
+use global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS, MAX_SERIAL_DATA_BYTES},
+    enums::*,
+};
+use glyphs::{segment, FourteenSegment, GlyphTable, SevenSegment};
+use matrix::Framebuffer;
+use stream::FrameStream;
+use wiring::Wiring;
 
-pub struct MAX7219<CONNECTOR> {
+///
+/// Driver for a chain of MAX7219/MAX7221 displays.
+///
+/// `DEVICES` is the number of displays the driver keeps settings for, `MAX_DISPLAYS`
+/// by default. Only displays combined from several chains with `from_chains` can
+/// exceed it, each chain being limited to `MAX_DISPLAYS` devices.
+///
+pub struct MAX7219<CONNECTOR, const DEVICES: usize = MAX_DISPLAYS> {
     c: CONNECTOR,
+    // requested intensity and scan limit per device, used for brightness compensation
+    intensity: [Option<Intensity>; DEVICES],
+    scan_limit: [ScanLimit; DEVICES],
+    compensate_intensity: [bool; DEVICES],
+    wiring: [Wiring; DEVICES],
 }
 
-impl<CONNECTOR> MAX7219<CONNECTOR>
+impl<CONNECTOR, const DEVICES: usize> MAX7219<CONNECTOR, DEVICES>
 where
     CONNECTOR: Connector,
 {
-    // internal constructor, users should call ::from_pins or ::from_spi
-    fn new(connector: CONNECTOR) -> Result<Self, DriverError> {
-        let mut max7219 = MAX7219 { c: connector };
-        max7219.init()?;
-        Ok(max7219)
-    }
-    fn init(&mut self) -> Result<(), DriverError> {
-        for i in 0..self.c.devices() {
-            self.set_display_test_mode(i, DisplayTest::NormalOperationMode)?; // turn testmode off
-            self.set_decode_mode(i, DecodeMode::NoDecode)?; // direct decode
-            self.set_scan_limit(i, ScanLimit::Display0To7)?; // set scanlimit
-            self.clear_display(i)?; // clear all digits
+    // internal constructor without init, users should call ::from_spi or use Max7219Builder
+    pub(crate) fn new(connector: CONNECTOR) -> Self {
+        MAX7219 {
+            c: connector,
+            intensity: [None; DEVICES],
+            scan_limit: [ScanLimit::Display0To7; DEVICES],
+            compensate_intensity: [false; DEVICES],
+            wiring: [Wiring::STANDARD; DEVICES],
         }
-        self.power_off()?; // power off
-        Ok(())
+    }
+
+    ///
+    /// Consumes the driver and returns the connector, leaving the displays in their current state.
+    /// Use the connector's `release()` to get the SPI interface and pins back.
+    ///
+    pub fn into_inner(self) -> CONNECTOR {
+        self.c
+    }
+
+    ///
+    /// Get the number of displays connected in series.
+    ///
+    pub fn devices(&self) -> usize {
+        self.c.devices()
+    }
+
+    ///
+    /// Get the chip variant of the `device_addr` display, which may differ between
+    /// the chains of a `MultiConnector`.
+    ///
+    pub fn chip(&self, device_addr: usize) -> Chip {
+        self.c.chip(device_addr)
     }
 
     ///
@@ -90,10 +149,15 @@ where
     /// * `mode` - one of the options in the `Intensity` enum
     ///
     pub fn set_intensity_all(&mut self, mode: Intensity) -> Result<(), DriverError> {
-        self.set_all_devices(RegisterAddr::Intensity, mode as u8)
+        for i in 0..self.c.devices() {
+            self.set_intensity(i, mode)?;
+        }
+        Ok(())
     }
     ///
     /// Configures the intensity of the LEDs on the `device_addr` arg.
+    /// If scan limit compensation is enabled, the intensity written is adjusted
+    /// to the scan limit of the display, see `set_intensity_compensation`.
     ///
     /// # Arguments
     ///
@@ -105,8 +169,41 @@ where
         device_addr: usize,
         mode: Intensity,
     ) -> Result<(), DriverError> {
-        self.c
-            .write_register_data(device_addr, RegisterAddr::Intensity, mode as u8)
+        if let Some(intensity) = self.intensity.get_mut(device_addr) {
+            *intensity = Some(mode);
+        }
+        self.c.write_register_data(
+            device_addr,
+            RegisterAddr::Intensity,
+            self.effective_intensity(device_addr, mode) as u8,
+        )
+    }
+
+    ///
+    /// Enables or disables intensity compensation for the scan limit.
+    ///
+    /// A display scanning fewer digits lights each digit for longer and appears
+    /// brighter at the same `Intensity`. With compensation enabled, the intensity
+    /// of each display is scaled down so that displays with different scan limits
+    /// in one chain look uniform, see `Intensity::compensated_for`.
+    /// Intensities already set are rewritten immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to compensate the intensity for the scan limit
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn set_intensity_compensation(&mut self, enabled: bool) -> Result<(), DriverError> {
+        self.compensate_intensity = [enabled; DEVICES];
+        for i in 0..self.c.devices().min(DEVICES) {
+            if let Some(mode) = self.intensity[i] {
+                self.set_intensity(i, mode)?;
+            }
+        }
+        Ok(())
     }
 
     ///
@@ -114,6 +211,12 @@ where
     /// Applicable mostly to seven segment displays if certain digits (ex. on the left)
     /// need not to be shown.
     ///
+    /// * `NOTE` - the scan limit sets how many digits are physically present and must
+    ///   not be used to blank digits, as it changes the brightness of the remaining
+    ///   ones. With fewer than 4 digits scanned, the segment current set by RSET must
+    ///   be reduced as described in the datasheet. Enable `set_intensity_compensation`
+    ///   to keep the brightness uniform across scan limits.
+    ///
     /// # Arguments
     ///
     /// * `device_addr` - display to address as connected in series (0 -> last)
@@ -124,8 +227,17 @@ where
         device_addr: usize,
         mode: ScanLimit,
     ) -> Result<(), DriverError> {
+        if let Some(scan_limit) = self.scan_limit.get_mut(device_addr) {
+            *scan_limit = mode;
+        }
         self.c
-            .write_register_data(device_addr, RegisterAddr::ScanLimit, mode as u8)
+            .write_register_data(device_addr, RegisterAddr::ScanLimit, mode as u8)?;
+        match self.intensity.get(device_addr) {
+            Some(Some(intensity)) if self.compensate_intensity[device_addr] => {
+                self.set_intensity(device_addr, *intensity)
+            }
+            _ => Ok(()),
+        }
     }
 
     ///
@@ -203,6 +315,22 @@ where
         self.c.write_register_data(device_addr, register_addr, data)
     }
     ///
+    /// Writes one register per device with a single chain write, latched at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - `[register_addr, data]` per device starting with device 0, `devices() * 2`
+    ///   bytes long. Devices to leave untouched get `RegisterAddr::NoOp`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `frame` is not `devices() * 2` bytes long
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_chain(&mut self, frame: &[u8]) -> Result<(), DriverError> {
+        self.c.write_chain(frame)
+    }
+    ///
     /// Writes a raw value to the display
     ///
     /// # Arguments
@@ -215,10 +343,65 @@ where
         device_addr: usize,
         raw: &[u8; MAX_DIGITS],
     ) -> Result<(), DriverError> {
-        let mut digit: u8 = 1;
-        for b in raw {
-            self.c.write_raw(device_addr, digit, *b)?;
-            digit += 1;
+        for (digit, b) in raw.iter().enumerate() {
+            self.c.write_raw(device_addr, digit as u8 + 1, *b)?;
+        }
+        Ok(())
+    }
+    ///
+    /// Writes a framebuffer to the displays, each module to the device of the same index.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the framebuffer to write, covering at most all connected displays
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the framebuffer covers more displays
+    ///   than are connected
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_frame<const MODULES: usize>(
+        &mut self,
+        frame: &Framebuffer<MODULES>,
+    ) -> Result<(), DriverError> {
+        if frame.devices() > self.c.devices() {
+            return Err(DriverError::InvalidValue);
+        }
+        if frame.devices() == self.c.devices() {
+            let mut stream = FrameStream::<MODULES>::with_capacity(frame.devices())?;
+            stream.encode_framebuffer(frame)?;
+            return self.write_stream(&stream);
+        }
+        for i in 0..frame.devices() {
+            self.write_raw_all(i, frame.device_rows(i))?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Writes a precomputed full refresh, one chain write per digit row
+    /// instead of one per digit and device.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - the encoded refresh, covering all connected displays
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the stream does not cover all
+    ///   connected displays
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_stream<const STREAM_DEVICES: usize>(
+        &mut self,
+        stream: &FrameStream<STREAM_DEVICES>,
+    ) -> Result<(), DriverError> {
+        if stream.devices() != self.c.devices() {
+            return Err(DriverError::InvalidValue);
+        }
+        for row in 0..MAX_DIGITS {
+            self.c.write_chain(stream.row_frame(row)?)?;
         }
         Ok(())
     }
@@ -228,7 +411,8 @@ where
     /// # Arguments
     ///
     /// * `device_addr` - display to address as connected in series (0 -> last)
-    /// * `string` - the byte string to send 8 bytes long. Unknown characters result in question mark.
+    /// * `string` - the byte string to send 8 bytes long, rendered with the `SevenSegment`
+    ///   glyph table. Unknown characters result in question mark.
     /// * `dots` - u8 bit array specifying where to put dots in the string (1 = dot, 0 = not)
     ///
     /// # Errors
@@ -238,9 +422,14 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use embedded_hal::blocking::spi::Write;
+    /// # use embedded_hal::digital::v2::OutputPin;
+    /// # use max7219_driver::MAX7219;
+    /// # fn example<SPI: Write<u8>, CS: OutputPin>(spi: SPI, cs: CS) {
     /// let mut max7219 = MAX7219::from_spi_cs(1, spi, cs).unwrap();
     /// max7219.power_on();
     /// max7219.write_str(0, b"12345678", 0b0001_0000).unwrap();
+    /// # }
     /// ```
     ///
     pub fn write_str(
@@ -249,16 +438,103 @@ where
         string: &[u8; MAX_DIGITS],
         dots: u8,
     ) -> Result<(), DriverError> {
-        self.set_decode_mode(0, DecodeMode::NoDecode)?;
+        self.write_str_with(device_addr, string, dots, &SevenSegment)
+    }
+    ///
+    /// Writes byte string to the display using a custom glyph table
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `string` - the byte string to send 8 bytes long, each byte read as a Latin-1 character
+    /// * `dots` - u8 bit array specifying where to put dots in the string (1 = dot, 0 = not)
+    /// * `glyphs` - the seven segment glyph table used to render each character
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_str_with<G>(
+        &mut self,
+        device_addr: usize,
+        string: &[u8; MAX_DIGITS],
+        dots: u8,
+        glyphs: &G,
+    ) -> Result<(), DriverError>
+    where
+        G: GlyphTable<Glyph = u8>,
+    {
+        self.set_decode_mode(device_addr, DecodeMode::NoDecode)?;
 
-        let mut digit: u8 = MAX_DIGITS as u8;
         let mut dot_product: u8 = 0b1000_0000;
-        for b in string {
-            let dot = (dots & dot_product) > 0;
+        for (position, b) in string.iter().enumerate() {
+            let mut segments = glyphs.encode(*b as char);
+            if (dots & dot_product) > 0 {
+                segments |= segment::DP; // turn "." on
+            }
             dot_product >>= 1;
-            self.c.write_raw(device_addr, digit, ssb_byte(*b, dot))?;
+            self.write_segments(device_addr, position, segments)?;
+        }
+
+        Ok(())
+    }
+    ///
+    /// Writes byte string to a fourteen segment display, each character spanning two
+    /// digit registers
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `string` - the byte string to send 4 bytes long, rendered with the `FourteenSegment`
+    ///   glyph table. Unknown characters result in question mark.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_str_14seg(
+        &mut self,
+        device_addr: usize,
+        string: &[u8; MAX_DIGITS / 2],
+    ) -> Result<(), DriverError> {
+        self.write_str_wide_with(device_addr, string, &FourteenSegment)
+    }
+    ///
+    /// Writes byte string to a display with up to 16 segments per character using a
+    /// custom glyph table
+    ///
+    /// Character `n` (0 = leftmost) spans the digit positions `2n` and `2n + 1` of the
+    /// device wiring: the low byte of its glyph is written to the first one and the high
+    /// byte to the second, bit 7 of each driving `SEG DP` and bit 0 `SEG G`.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `string` - the byte string to send 4 bytes long, each byte read as a Latin-1 character
+    /// * `glyphs` - the glyph table used to render each character
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_str_wide_with<G>(
+        &mut self,
+        device_addr: usize,
+        string: &[u8; MAX_DIGITS / 2],
+        glyphs: &G,
+    ) -> Result<(), DriverError>
+    where
+        G: GlyphTable<Glyph = u16>,
+    {
+        self.set_decode_mode(device_addr, DecodeMode::NoDecode)?;
 
-            digit -= 1;
+        let wiring = self.wiring_of(device_addr);
+        for (position, b) in string.iter().enumerate() {
+            let [low, high] = glyphs.encode(*b as char).to_le_bytes();
+            self.c
+                .write_raw(device_addr, wiring.digit_register(2 * position)?, low)?;
+            self.c
+                .write_raw(device_addr, wiring.digit_register(2 * position + 1)?, high)?;
         }
 
         Ok(())
@@ -270,8 +546,11 @@ where
     ///
     /// * `device_addr` - display to address as connected in series (0 -> last)
     /// * `bcd` - the bcd encoded string slice consisting of [0-9,-,E,L,H,P]
-    /// where upper case input for alphabetic characters results in dot being set.
-    /// Length of string is always 8 bytes, use spaces for blanking.
+    ///   where upper case input for alphabetic characters results in dot being set.
+    ///   Length of string is always 8 bytes, use spaces for blanking.
+    ///
+    /// The digit order of the device wiring is respected, the segment order cannot be
+    /// as the chip decodes the segments itself.
     ///
     /// # Errors
     ///
@@ -280,9 +559,14 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use embedded_hal::blocking::spi::Write;
+    /// # use embedded_hal::digital::v2::OutputPin;
+    /// # use max7219_driver::MAX7219;
+    /// # fn example<SPI: Write<u8>, CS: OutputPin>(spi: SPI, cs: CS) {
     /// let mut max7219 = MAX7219::from_spi_cs(1, spi, cs).unwrap();
     /// max7219.power_on();
     /// max7219.write_bcd(0, b"H_e_l_P?").unwrap();
+    /// # }
     /// ```
     ///
     pub fn write_bcd(
@@ -290,17 +574,91 @@ where
         device_addr: usize,
         bcd: &[u8; MAX_DIGITS],
     ) -> Result<(), DriverError> {
-        self.set_decode_mode(0, DecodeMode::CodeBDigits7_0)?;
+        self.set_decode_mode(device_addr, DecodeMode::CodeBDigits7_0)?;
 
-        let mut digit: u8 = MAX_DIGITS as u8;
-        for b in bcd {
+        for (position, b) in bcd.iter().enumerate() {
+            let digit = self.wiring_of(device_addr).digit_register(position)?;
             self.c.write_raw(device_addr, digit, bcd_byte(*b))?;
-
-            digit -= 1;
         }
 
         Ok(())
     }
+    ///
+    /// Configures how the digits and segments of the `device_addr` display are wired.
+    /// All text and BCD output to the display is mapped through it.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `wiring` - the digit and segment wiring of the display
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `device_addr` is out of range
+    ///
+    pub fn set_wiring(&mut self, device_addr: usize, wiring: Wiring) -> Result<(), DriverError> {
+        match self.wiring.get_mut(device_addr) {
+            Some(w) => {
+                *w = wiring;
+                Ok(())
+            }
+            None => Err(DriverError::InvalidValue),
+        }
+    }
+    ///
+    /// Writes a segment pattern in standard order to the character `position`
+    /// (0 = leftmost) of the `device_addr` display, mapped through its wiring.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_addr` - display to address as connected in series (0 -> last)
+    /// * `position` - character position in the range 0..=7, counted from the left
+    /// * `segments` - segment pattern in `DP A B C D E F G` order
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `position` is out of range
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write_segments(
+        &mut self,
+        device_addr: usize,
+        position: usize,
+        segments: u8,
+    ) -> Result<(), DriverError> {
+        let wiring = self.wiring_of(device_addr);
+        self.c.write_raw(
+            device_addr,
+            wiring.digit_register(position)?,
+            wiring.map_segments(segments),
+        )
+    }
+    // copies per-device settings of another driver to the devices from `to` on
+    fn copy_device_state(
+        &mut self,
+        to: usize,
+        intensity: &[Option<Intensity>],
+        scan_limit: &[ScanLimit],
+        compensate_intensity: &[bool],
+        wiring: &[Wiring],
+    ) {
+        let count = DEVICES.saturating_sub(to).min(intensity.len());
+        self.intensity[to..to + count].copy_from_slice(&intensity[..count]);
+        self.scan_limit[to..to + count].copy_from_slice(&scan_limit[..count]);
+        self.compensate_intensity[to..to + count].copy_from_slice(&compensate_intensity[..count]);
+        self.wiring[to..to + count].copy_from_slice(&wiring[..count]);
+    }
+    fn wiring_of(&self, device_addr: usize) -> Wiring {
+        self.wiring.get(device_addr).copied().unwrap_or_default()
+    }
+    fn effective_intensity(&self, device_addr: usize, mode: Intensity) -> Intensity {
+        match self.scan_limit.get(device_addr) {
+            Some(scan_limit) if self.compensate_intensity[device_addr] => {
+                mode.compensated_for(*scan_limit, self.c.chip(device_addr))
+            }
+            _ => mode,
+        }
+    }
     fn set_all_devices(
         &mut self,
         register_address: RegisterAddr,
@@ -313,6 +671,62 @@ where
     }
 }
 
+impl<CONNECTOR, const DEVICES: usize> MAX7219<CONNECTOR, DEVICES>
+where
+    CONNECTOR: LoopbackConnector,
+{
+    ///
+    /// Counts the devices in the chain by shifting a marker through it and
+    /// counting the 16-bit frames until it comes back on MISO.
+    /// Only no-op frames are latched, the displays are left untouched.
+    ///
+    /// * `NOTE` - requires the `DOUT` of the last device wired back to MISO and
+    ///   an SPI supporting full-duplex transfers.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::Chain` - returned if the marker did not come back within
+    ///   `MAX_DISPLAYS` devices, e.g. because of a broken link
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn detect_chain_length(&mut self) -> Result<usize, DriverError> {
+        // fill every device with no-ops, so that nothing but zeros precedes the marker
+        let mut buffer = [0; MAX_SERIAL_DATA_BYTES + 2];
+        self.c.transfer_raw(&mut buffer)?;
+
+        buffer = [0; MAX_SERIAL_DATA_BYTES + 2];
+        buffer[..2].copy_from_slice(&LOOPBACK_MARKER);
+        self.c.transfer_raw(&mut buffer)?;
+
+        for (devices, frame) in buffer.chunks_exact(2).enumerate() {
+            match frame {
+                [0, 0] => continue,
+                _ if frame == LOOPBACK_MARKER => return Ok(devices),
+                _ => break,
+            }
+        }
+        Err(DriverError::Chain)
+    }
+
+    ///
+    /// Checks that the chain holds as many devices as the driver was constructed for.
+    ///
+    /// * `NOTE` - requires the `DOUT` of the last device wired back to MISO, see
+    ///   `detect_chain_length`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::Chain` - returned if the chain is broken or its length differs
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn verify_chain(&mut self) -> Result<(), DriverError> {
+        if self.detect_chain_length()? != self.c.devices() {
+            return Err(DriverError::Chain);
+        }
+        Ok(())
+    }
+}
+
 impl<SPI> MAX7219<SpiConnector<SPI>>
 where
     SPI: Write<u8>,
@@ -321,6 +735,7 @@ where
     /// Construct a new MAX7219 driver instance from pre-existing SPI in full hardware mode.
     /// The SPI will control CS (LOAD) line according to it's internal mode set.
     /// If you need the CS line to be controlled manually use MAX7219::from_spi_cs
+    /// To choose the initial display configuration use Max7219Builder::from_spi
     ///
     /// * `NOTE` - make sure the SPI is initialized in MODE_0 with max 10 Mhz frequency.
     ///
@@ -334,7 +749,50 @@ where
     /// * `DriverError` - returned in case there was an error during data transfer
     ///
     pub fn from_spi(displays: usize, spi: SPI) -> Result<Self, DriverError> {
-        MAX7219::new(SpiConnector::new(displays, spi))
+        MAX7219::from_spi_chip(displays, spi, Chip::Max7219)
+    }
+
+    ///
+    /// Construct a new driver instance in full hardware CS mode for the given chip variant.
+    /// This is the recommended mode for the MAX7221, whose CS is SPI compliant.
+    ///
+    /// * `NOTE` - the hardware CS must stay asserted for the whole chain write,
+    ///   not be toggled per byte or per 16-bit word.
+    ///
+    /// # Arguments
+    ///
+    /// * `displays` - number of displays connected in series
+    /// * `spi` - the SPI interface initialized with MOSI, MISO(unused) and CLK
+    /// * `chip` - the chip variant connected, see `Chip`
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn from_spi_chip(displays: usize, spi: SPI, chip: Chip) -> Result<Self, DriverError> {
+        Max7219Builder::from_spi_chip(displays, spi, chip).build()
+    }
+
+    ///
+    /// Releases the SPI interface, leaving the displays in their current state.
+    ///
+    pub fn release(self) -> SPI {
+        self.c.release()
+    }
+
+    ///
+    /// Powers off all displays, then releases the SPI interface.
+    ///
+    /// # Errors
+    ///
+    /// * `(DriverError, _)` - returned with the released peripherals in case the displays
+    ///   could not be powered off, so that they are not lost
+    ///
+    pub fn shutdown_and_release(mut self) -> Result<SPI, (DriverError, SPI)> {
+        match self.power_off() {
+            Ok(()) => Ok(self.release()),
+            Err(e) => Err((e, self.release())),
+        }
     }
 }
 
@@ -347,6 +805,7 @@ where
     /// Construct a new MAX7219 driver instance from pre-existing SPI and CS pin
     /// set to output. This version of the connection uses the CS pin manually
     /// to avoid issues with how the CS mode is handled in hardware SPI implementations.
+    /// CS is toggled back to back, use MAX7219::from_spi_cs_timed to enforce minimum CS timings.
     ///
     /// * `NOTE` - make sure the SPI is initialized in MODE_0 with max 10 Mhz frequency.
     ///
@@ -361,10 +820,304 @@ where
     /// * `DriverError` - returned in case there was an error during data transfer
     ///
     pub fn from_spi_cs(displays: usize, spi: SPI, cs: CS) -> Result<Self, DriverError> {
-        MAX7219::new(SpiConnectorCs::new(displays, spi, cs))
+        MAX7219::from_spi_cs_chip(displays, spi, cs, Chip::Max7219)
+    }
+
+    ///
+    /// Construct a new driver instance with a manually controlled CS pin for the given chip variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `displays` - number of displays connected in series
+    /// * `spi` - the SPI interface initialized with MOSI, MISO(unused) and CLK
+    /// * `cs` - the CS PIN used to LOAD register on the display set to output mode
+    /// * `chip` - the chip variant connected, see `Chip`
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn from_spi_cs_chip(
+        displays: usize,
+        spi: SPI,
+        cs: CS,
+        chip: Chip,
+    ) -> Result<Self, DriverError> {
+        Max7219Builder::from_spi_cs_chip(displays, spi, cs, chip).build()
     }
 }
 
+impl<SPI, CS, DELAY> MAX7219<SpiConnectorCs<SPI, CS, DELAY>>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+    DELAY: DelayUs<u32>,
+{
+    ///
+    /// Construct a new driver instance with a manually controlled CS pin whose
+    /// minimum setup, hold and high times are enforced with `delay`.
+    /// Use this when fast MCUs or long cables corrupt data with MAX7219::from_spi_cs.
+    ///
+    /// # Arguments
+    ///
+    /// * `displays` - number of displays connected in series
+    /// * `spi` - the SPI interface initialized with MOSI, MISO(unused) and CLK
+    /// * `cs` - the CS PIN used to LOAD register on the display set to output mode
+    /// * `chip` - the chip variant connected, see `Chip`
+    /// * `delay` - the delay provider used to wait between CS edges
+    /// * `timing` - the minimum CS timings, see `CsTiming`
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn from_spi_cs_timed(
+        displays: usize,
+        spi: SPI,
+        cs: CS,
+        chip: Chip,
+        delay: DELAY,
+        timing: CsTiming,
+    ) -> Result<Self, DriverError> {
+        Max7219Builder::from_spi_cs_timed(displays, spi, cs, chip, delay, timing).build()
+    }
+
+    ///
+    /// Releases the SPI interface and CS pin, leaving the displays in their current state.
+    ///
+    pub fn release(self) -> (SPI, CS) {
+        self.c.release()
+    }
+
+    ///
+    /// Powers off all displays, then releases the SPI interface and CS pin.
+    ///
+    /// # Errors
+    ///
+    /// * `(DriverError, _)` - returned with the released peripherals in case the displays
+    ///   could not be powered off, so that they are not lost
+    ///
+    pub fn shutdown_and_release(mut self) -> Result<(SPI, CS), (DriverError, (SPI, CS))> {
+        match self.power_off() {
+            Ok(()) => Ok(self.release()),
+            Err(e) => Err((e, self.release())),
+        }
+    }
+
+    ///
+    /// Releases the SPI interface, CS pin and delay provider, leaving the displays
+    /// in their current state.
+    ///
+    pub fn release_with_delay(self) -> (SPI, CS, DELAY) {
+        self.c.release_with_delay()
+    }
+
+    ///
+    /// Powers off all displays, then releases the SPI interface, CS pin and delay provider.
+    ///
+    /// # Errors
+    ///
+    /// * `(DriverError, _)` - returned with the released peripherals in case the displays
+    ///   could not be powered off, so that they are not lost
+    ///
+    #[allow(clippy::type_complexity)]
+    pub fn shutdown_and_release_with_delay(
+        mut self,
+    ) -> Result<(SPI, CS, DELAY), (DriverError, (SPI, CS, DELAY))> {
+        match self.power_off() {
+            Ok(()) => Ok(self.release_with_delay()),
+            Err(e) => Err((e, self.release_with_delay())),
+        }
+    }
+}
+
+impl<BUS, CS> MAX7219<SharedSpiConnectorCs<BUS, CS>>
+where
+    BUS: BusLock,
+    CS: OutputPin,
+{
+    ///
+    /// Construct a new MAX7219 driver instance on an SPI bus shared with other drivers.
+    /// The bus is only locked for the duration of each chain write, and the CS pin is
+    /// controlled manually as with MAX7219::from_spi_cs.
+    ///
+    /// * `NOTE` - the bus must be configured in MODE_0 with max 10 Mhz frequency
+    ///   whenever it is handed to this driver.
+    ///
+    /// # Arguments
+    ///
+    /// * `displays` - number of displays connected in series
+    /// * `bus` - the shared bus, e.g. `&RefCell<SPI>` or, with the `critical-section`
+    ///   feature, `&critical_section::Mutex<RefCell<SPI>>`
+    /// * `cs` - the CS PIN used to LOAD register on the display set to output mode
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::cell::RefCell;
+    /// # use embedded_hal::blocking::spi::Write;
+    /// # use embedded_hal::digital::v2::OutputPin;
+    /// # use max7219_driver::MAX7219;
+    /// # fn example<SPI: Write<u8>, CS: OutputPin>(spi: SPI, cs: CS) {
+    /// let bus = RefCell::new(spi);
+    /// let mut max7219 = MAX7219::from_shared_spi_cs(1, &bus, cs).unwrap();
+    /// // `&bus` can be lent to the other drivers on the bus as well, e.g. an SD card
+    /// # }
+    /// ```
+    ///
+    pub fn from_shared_spi_cs(displays: usize, bus: BUS, cs: CS) -> Result<Self, DriverError> {
+        MAX7219::from_shared_spi_cs_chip(displays, bus, cs, Chip::Max7219)
+    }
+
+    ///
+    /// Construct a new driver instance on a shared SPI bus for the given chip variant.
+    /// A MAX7221 ignores other traffic on the bus, while a MAX7219 shifts it in
+    /// but only latches the frame written by this driver.
+    ///
+    /// # Arguments
+    ///
+    /// * `displays` - number of displays connected in series
+    /// * `bus` - the shared bus handle
+    /// * `cs` - the CS PIN used to LOAD register on the display set to output mode
+    /// * `chip` - the chip variant connected, see `Chip`
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn from_shared_spi_cs_chip(
+        displays: usize,
+        bus: BUS,
+        cs: CS,
+        chip: Chip,
+    ) -> Result<Self, DriverError> {
+        Max7219Builder::from_shared_spi_cs_chip(displays, bus, cs, chip).build()
+    }
+
+    ///
+    /// Releases the bus handle and CS pin, leaving the displays in their current state.
+    ///
+    pub fn release(self) -> (BUS, CS) {
+        self.c.release()
+    }
+
+    ///
+    /// Powers off all displays, then releases the bus handle and CS pin.
+    ///
+    /// # Errors
+    ///
+    /// * `(DriverError, _)` - returned with the released peripherals in case the displays
+    ///   could not be powered off, so that they are not lost
+    ///
+    pub fn shutdown_and_release(mut self) -> Result<(BUS, CS), (DriverError, (BUS, CS))> {
+        match self.power_off() {
+            Ok(()) => Ok(self.release()),
+            Err(e) => Err((e, self.release())),
+        }
+    }
+}
+
+impl<FIRST, SECOND, const DEVICES: usize> MAX7219<MultiConnector<FIRST, SECOND>, DEVICES>
+where
+    FIRST: Connector,
+    SECOND: Connector,
+{
+    ///
+    /// Combine two drivers on independent chains into one logical display.
+    /// The devices of `first` keep their indices, those of `second` follow them.
+    /// Chains can be combined further by passing a combined driver again.
+    ///
+    /// * `NOTE` - both drivers must already be initialized, no register is written.
+    ///   Per-device settings such as intensity, its compensation and wiring are kept.
+    ///   The combined driver holds up to `DEVICES` displays, so displays of more than
+    ///   `MAX_DISPLAYS` devices need the capacity spelled out, e.g. `MAX7219<_, 16>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - the driver of the chain holding the first devices
+    /// * `second` - the driver of the chain holding the following devices
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the chains hold more than `DEVICES`
+    ///   devices together
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use embedded_hal::blocking::spi::Write;
+    /// # use embedded_hal::digital::v2::OutputPin;
+    /// # use max7219_driver::matrix::Framebuffer;
+    /// # use max7219_driver::MAX7219;
+    /// # fn example<SPI: Write<u8>, CS: OutputPin>(spi2: SPI, cs_top: CS, spi3: SPI, cs_bottom: CS) {
+    /// let top = MAX7219::from_spi_cs(8, spi2, cs_top).unwrap();
+    /// let bottom = MAX7219::from_spi_cs(8, spi3, cs_bottom).unwrap();
+    /// let mut sign: MAX7219<_, 16> = MAX7219::from_chains(top, bottom).unwrap();
+    /// sign.write_frame(&Framebuffer::<16>::with_capacity(8, 2).unwrap()).unwrap();
+    /// # }
+    /// ```
+    ///
+    pub fn from_chains<const FIRST_DEVICES: usize, const SECOND_DEVICES: usize>(
+        first: MAX7219<FIRST, FIRST_DEVICES>,
+        second: MAX7219<SECOND, SECOND_DEVICES>,
+    ) -> Result<Self, DriverError> {
+        if first.devices() + second.devices() > DEVICES {
+            return Err(DriverError::InvalidValue);
+        }
+        let offset = first.devices();
+        let mut max7219 = MAX7219::new(MultiConnector::new(first.c, second.c));
+        max7219.copy_device_state(
+            0,
+            &first.intensity,
+            &first.scan_limit,
+            &first.compensate_intensity,
+            &first.wiring,
+        );
+        // the second chain is copied last, overwriting whatever lies past the first one
+        max7219.copy_device_state(
+            offset,
+            &second.intensity,
+            &second.scan_limit,
+            &second.compensate_intensity,
+            &second.wiring,
+        );
+        Ok(max7219)
+    }
+
+    ///
+    /// Split the logical display back into the drivers of its two chains,
+    /// keeping the per-device settings.
+    ///
+    pub fn split(self) -> (MAX7219<FIRST, DEVICES>, MAX7219<SECOND, DEVICES>) {
+        let (first, second) = self.c.release();
+        let offset = first.devices();
+        let mut first = MAX7219::new(first);
+        first.copy_device_state(
+            0,
+            &self.intensity[..offset],
+            &self.scan_limit[..offset],
+            &self.compensate_intensity[..offset],
+            &self.wiring[..offset],
+        );
+        let mut second = MAX7219::new(second);
+        second.copy_device_state(
+            0,
+            &self.intensity[offset..],
+            &self.scan_limit[offset..],
+            &self.compensate_intensity[offset..],
+            &self.wiring[offset..],
+        );
+        (first, second)
+    }
+}
+
+/// no-op frame with a data pattern unlikely to come from a floating MISO
+const LOOPBACK_MARKER: [u8; 2] = [RegisterAddr::NoOp as u8, 0xA5];
+
 ///
 /// Translate alphanumeric ASCII bytes into BCD
 /// encoded bytes expected by the display chip.
@@ -384,58 +1137,3 @@ fn bcd_byte(b: u8) -> u8 {
         _ => b,
     }
 }
-
-///
-/// Translate alphanumeric ASCII bytes into segment set bytes
-///
-fn ssb_byte(b: u8, dot: bool) -> u8 {
-    let mut result = match b as char {
-        ' ' => 0b0000_0000, // "blank"
-        '.' => 0b1000_0000,
-        '-' => 0b0000_0001, // -
-        '_' => 0b0000_1000, // _
-        '0' => 0b0111_1110,
-        '1' => 0b0011_0000,
-        '2' => 0b0110_1101,
-        '3' => 0b0111_1001,
-        '4' => 0b0011_0011,
-        '5' => 0b0101_1011,
-        '6' => 0b0101_1111,
-        '7' => 0b0111_0000,
-        '8' => 0b0111_1111,
-        '9' => 0b0111_1011,
-        'a' | 'A' => 0b0111_0111,
-        'b' => 0b0001_1111,
-        'c' | 'C' => 0b0100_1110,
-        'd' => 0b0011_1101,
-        'e' | 'E' => 0b0100_1111,
-        'f' | 'F' => 0b0100_0111,
-        'g' | 'G' => 0b0101_1110,
-        'h' | 'H' => 0b0011_0111,
-        'i' | 'I' => 0b0011_0000,
-        'j' | 'J' => 0b0011_1100,
-        // K undoable
-        'l' | 'L' => 0b0000_1110,
-        // M undoable
-        'n' | 'N' => 0b0001_0101,
-        'o' | 'O' => 0b0111_1110,
-        'p' | 'P' => 0b0110_0111,
-        'q' => 0b0111_0011,
-        // R undoable
-        's' | 'S' => 0b0101_1011,
-        // T undoable
-        'u' | 'U' => 0b0011_1110,
-        // V undoable
-        // W undoable
-        // X undoable
-        // Y undoable
-        // Z undoable
-        _ => 0b1110_0101, // ?
-    };
-
-    if dot {
-        result |= 0b1000_0000; // turn "." on
-    }
-
-    result
-}
@@ -0,0 +1,239 @@
+use crate::font::Font;
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::DriverError,
+};
+
+///
+/// A rectangular area of a framebuffer, in pixels.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    /// left edge
+    pub x: i32,
+    /// top edge
+    pub y: i32,
+    /// width, nothing is inside if not positive
+    pub width: i32,
+    /// height, nothing is inside if not positive
+    pub height: i32,
+}
+
+impl Rect {
+    ///
+    /// Creates a rectangle from its top left corner and size.
+    ///
+    pub const fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    ///
+    /// Get whether the pixel at `(x, y)` is inside of the rectangle.
+    ///
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width && y < self.y + self.height
+    }
+
+    ///
+    /// Get the area covered by both rectangles, empty if they do not overlap.
+    ///
+    pub fn intersect(&self, other: Rect) -> Rect {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = self
+            .x
+            .saturating_add(self.width)
+            .min(other.x.saturating_add(other.width));
+        let bottom = self
+            .y
+            .saturating_add(self.height)
+            .min(other.y.saturating_add(other.height));
+        Rect::new(
+            x,
+            y,
+            right.saturating_sub(x).max(0),
+            bottom.saturating_sub(y).max(0),
+        )
+    }
+}
+
+///
+/// A pixel buffer for 8x8 matrix modules arranged in a grid.
+///
+/// Modules are laid out row by row: device 0 is the top left module, the next
+/// device is to its right, and so on. Within a module, digit register 1 (Digit0)
+/// is the top row and bit 7 the leftmost column, as written by `MAX7219::write_raw_all`.
+/// Coordinates outside of the buffer are clipped.
+///
+/// `MODULES` is the number of modules the buffer can hold, `MAX_DISPLAYS` by default.
+/// Grids of more modules, e.g. for chains combined with `MAX7219::from_chains`, are
+/// created with `with_capacity`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Framebuffer<const MODULES: usize = MAX_DISPLAYS> {
+    modules_wide: usize,
+    modules_high: usize,
+    rows: [[u8; MAX_DIGITS]; MODULES],
+}
+
+impl Framebuffer {
+    ///
+    /// Creates a blank framebuffer of up to `MAX_DISPLAYS` modules.
+    ///
+    /// # Arguments
+    ///
+    /// * `modules_wide` - number of modules side by side
+    /// * `modules_high` - number of modules stacked on top of each other
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the grid is empty or has more than
+    ///   `MAX_DISPLAYS` modules
+    ///
+    pub fn new(modules_wide: usize, modules_high: usize) -> Result<Self, DriverError> {
+        Framebuffer::with_capacity(modules_wide, modules_high)
+    }
+}
+
+impl<const MODULES: usize> Framebuffer<MODULES> {
+    ///
+    /// Creates a blank framebuffer of up to `MODULES` modules.
+    ///
+    /// # Arguments
+    ///
+    /// * `modules_wide` - number of modules side by side
+    /// * `modules_high` - number of modules stacked on top of each other
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the grid is empty or has more than
+    ///   `MODULES` modules
+    ///
+    pub fn with_capacity(modules_wide: usize, modules_high: usize) -> Result<Self, DriverError> {
+        let modules = modules_wide * modules_high;
+        if modules == 0 || modules > MODULES {
+            return Err(DriverError::InvalidValue);
+        }
+        Ok(Framebuffer {
+            modules_wide,
+            modules_high,
+            rows: [[0; MAX_DIGITS]; MODULES],
+        })
+    }
+
+    ///
+    /// Get the number of modules covered, which is the number of devices to flush.
+    ///
+    pub fn devices(&self) -> usize {
+        self.modules_wide * self.modules_high
+    }
+
+    ///
+    /// Get the width in pixels.
+    ///
+    pub fn width(&self) -> i32 {
+        (self.modules_wide * 8) as i32
+    }
+
+    ///
+    /// Get the height in pixels.
+    ///
+    pub fn height(&self) -> i32 {
+        (self.modules_high * MAX_DIGITS) as i32
+    }
+
+    ///
+    /// Turns every pixel off.
+    ///
+    pub fn clear(&mut self) {
+        self.rows = [[0; MAX_DIGITS]; MODULES];
+    }
+
+    ///
+    /// Get the digit register contents of the `device_addr` module.
+    ///
+    pub fn device_rows(&self, device_addr: usize) -> &[u8; MAX_DIGITS] {
+        &self.rows[device_addr]
+    }
+
+    ///
+    /// Get mutable access to the digit register contents of the `device_addr` module.
+    ///
+    pub fn device_rows_mut(&mut self, device_addr: usize) -> &mut [u8; MAX_DIGITS] {
+        &mut self.rows[device_addr]
+    }
+
+    ///
+    /// Get the area covered by the buffer.
+    ///
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width(), self.height())
+    }
+
+    ///
+    /// Get the state of a pixel, `false` outside of the buffer.
+    ///
+    pub fn pixel(&self, x: i32, y: i32) -> bool {
+        match self.locate(x, y) {
+            Some((device, row, mask)) => self.rows[device][row] & mask != 0,
+            None => false,
+        }
+    }
+
+    ///
+    /// Sets the state of a pixel, ignored outside of the buffer.
+    ///
+    pub fn set_pixel(&mut self, x: i32, y: i32, on: bool) {
+        if let Some((device, row, mask)) = self.locate(x, y) {
+            if on {
+                self.rows[device][row] |= mask;
+            } else {
+                self.rows[device][row] &= !mask;
+            }
+        }
+    }
+
+    ///
+    /// Draws a glyph column by column with its top left corner at `(x, y)`.
+    /// Only lit pixels are drawn, the background is left as is.
+    ///
+    pub fn draw_columns(&mut self, x: i32, y: i32, columns: &[u8], height: u8) {
+        for (dx, column) in columns.iter().enumerate() {
+            for dy in 0..height.min(8) {
+                if column & (1 << dy) != 0 {
+                    self.set_pixel(x + dx as i32, y + dy as i32, true);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Draws text with its top left corner at `(x, y)`.
+    /// Characters without a glyph in `font` are skipped.
+    ///
+    /// Returns the width of the text drawn in pixels, without trailing spacing.
+    ///
+    pub fn draw_text<F: Font>(&mut self, x: i32, y: i32, text: &str, font: &F) -> i32 {
+        let mut cursor = x;
+        for c in text.chars() {
+            if let Some(columns) = font.glyph(c) {
+                self.draw_columns(cursor, y, columns, font.height());
+                cursor += columns.len() as i32 + font.spacing() as i32;
+            }
+        }
+        (cursor - x - font.spacing() as i32).max(0)
+    }
+
+    fn locate(&self, x: i32, y: i32) -> Option<(usize, usize, u8)> {
+        if x < 0 || y < 0 || x >= self.width() || y >= self.height() {
+            return None;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let device = (y / MAX_DIGITS) * self.modules_wide + x / 8;
+        Some((device, y % MAX_DIGITS, 0b1000_0000 >> (x % 8)))
+    }
+}
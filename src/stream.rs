@@ -0,0 +1,172 @@
+//!
+//! Precomputed serial frames for full display refreshes.
+//!
+//! A refresh is encoded row-parallel: each of the 8 digit rows is one chain frame
+//! of `devices * 2` bytes that writes that digit register on every device at once,
+//! latched by one `CS`/`LOAD` pulse. The whole refresh is a contiguous buffer of
+//! 8 such frames that can be handed to a DMA-capable SPI transfer, pulsing `LOAD`
+//! after each `row_frame`.
+//!
+
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS, MAX_SERIAL_DATA_BYTES},
+    enums::{DriverError, RegisterAddr},
+};
+use crate::matrix::Framebuffer;
+
+/// Size of the largest encoded refresh, 8 rows of a full chain
+pub const MAX_STREAM_BYTES: usize = MAX_DIGITS * MAX_SERIAL_DATA_BYTES;
+
+///
+/// The serial frames of a full refresh of a chain.
+///
+/// `DEVICES` is the number of devices the stream can hold, `MAX_DISPLAYS` by default.
+/// Streams for more devices, e.g. for chains combined with `MAX7219::from_chains`,
+/// are created with `with_capacity`.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::spi::Write;
+/// # use embedded_hal::digital::v2::OutputPin;
+/// # use max7219_driver::global::consts::MAX_DIGITS;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::stream::FrameStream;
+/// # fn example<SPI: Write<u8>, CS: OutputPin>(dma_spi: &mut SPI, cs: &mut CS, frame: &Framebuffer)
+/// # where
+/// #     SPI::Error: core::fmt::Debug,
+/// #     CS::Error: core::fmt::Debug,
+/// # {
+/// let mut stream = FrameStream::new(4).unwrap();
+/// stream.encode_framebuffer(&frame).unwrap();
+/// for row in 0..MAX_DIGITS {
+///     cs.set_low().unwrap();
+///     dma_spi.write(stream.row_frame(row).unwrap()).unwrap();
+///     cs.set_high().unwrap();
+/// }
+/// # }
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct FrameStream<const DEVICES: usize = MAX_DISPLAYS> {
+    devices: usize,
+    // MAX_DIGITS frames of `devices * 2` bytes each, packed from the start
+    buffer: [[u8; MAX_DIGITS * 2]; DEVICES],
+}
+
+impl FrameStream {
+    ///
+    /// Creates a stream for a chain of `devices`, encoding blank displays.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `devices` is 0 or above `MAX_DISPLAYS`
+    ///
+    pub fn new(devices: usize) -> Result<Self, DriverError> {
+        FrameStream::with_capacity(devices)
+    }
+}
+
+impl<const DEVICES: usize> FrameStream<DEVICES> {
+    ///
+    /// Creates a stream for a chain of `devices`, encoding blank displays.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `devices` is 0 or above `DEVICES`
+    ///
+    pub fn with_capacity(devices: usize) -> Result<Self, DriverError> {
+        if devices == 0 || devices > DEVICES {
+            return Err(DriverError::InvalidValue);
+        }
+        let mut stream = FrameStream {
+            devices,
+            buffer: [[0; MAX_DIGITS * 2]; DEVICES],
+        };
+        stream.encode_rows(&[[0; MAX_DIGITS]; DEVICES][..devices])?;
+        Ok(stream)
+    }
+
+    ///
+    /// Get the number of devices in the chain.
+    ///
+    pub fn devices(&self) -> usize {
+        self.devices
+    }
+
+    ///
+    /// Encodes the digit register contents of every device, `rows[n]` being written
+    /// to device `n` as with `MAX7219::write_raw_all`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `rows` does not cover every device
+    ///
+    pub fn encode_rows(&mut self, rows: &[[u8; MAX_DIGITS]]) -> Result<(), DriverError> {
+        if rows.len() != self.devices {
+            return Err(DriverError::InvalidValue);
+        }
+        let frame_len = self.frame_len();
+        let buffer = self.buffer.as_flattened_mut();
+        for digit in 0..MAX_DIGITS {
+            let frame = &mut buffer[digit * frame_len..(digit + 1) * frame_len];
+            for (device_addr, device_rows) in rows.iter().enumerate() {
+                // Each device has two elements([register_addr,data]) need * 2
+                frame[device_addr * 2] = RegisterAddr::Digit0 as u8 + digit as u8;
+                frame[device_addr * 2 + 1] = device_rows[digit];
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Encodes a framebuffer, each module to the device of the same index.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the framebuffer does not cover every device
+    ///
+    pub fn encode_framebuffer<const MODULES: usize>(
+        &mut self,
+        frame: &Framebuffer<MODULES>,
+    ) -> Result<(), DriverError> {
+        if frame.devices() != self.devices {
+            return Err(DriverError::InvalidValue);
+        }
+        let mut rows = [[0; MAX_DIGITS]; DEVICES];
+        for (i, device_rows) in rows.iter_mut().enumerate().take(self.devices) {
+            *device_rows = *frame.device_rows(i);
+        }
+        self.encode_rows(&rows[..self.devices])
+    }
+
+    ///
+    /// Get the length of one chain frame in bytes, `devices * 2`.
+    ///
+    pub fn frame_len(&self) -> usize {
+        self.devices * 2
+    }
+
+    ///
+    /// Get the chain frame writing digit `row` (0 = Digit0) of every device,
+    /// to be followed by a `CS`/`LOAD` pulse.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `row` is not in the range 0..=7
+    ///
+    pub fn row_frame(&self, row: usize) -> Result<&[u8], DriverError> {
+        if row >= MAX_DIGITS {
+            return Err(DriverError::InvalidValue);
+        }
+        let frame_len = self.frame_len();
+        Ok(&self.buffer.as_flattened()[row * frame_len..(row + 1) * frame_len])
+    }
+
+    ///
+    /// Get the whole refresh as one contiguous buffer of 8 chain frames.
+    ///
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_flattened()[..MAX_DIGITS * self.frame_len()]
+    }
+}
@@ -0,0 +1,301 @@
+//!
+//! `core::fmt::Write` views of the displays, so that text can be formatted
+//! straight onto them with `write!`.
+//!
+//! Text is buffered until `flush`, which writes it in one go.
+//!
+
+use core::fmt;
+
+use crate::connectors::traits::Connector;
+use crate::font::{Font, Font5x7};
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::{DecodeMode, DriverError},
+};
+use crate::glyphs::{segment, GlyphTable, SevenSegment};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+///
+/// Formats text onto one seven segment display, from the leftmost digit on.
+///
+/// A `.` is folded into the decimal point of the preceding digit if it has none
+/// yet, so `"12.5"` takes three digits. Writing more than 8 digits fails with
+/// `fmt::Error`, digits left over on flush are blanked.
+///
+/// # Examples
+///
+/// ```
+/// # use core::fmt::Write;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::text::SegmentWriter;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, temp: f32) {
+/// let mut text = SegmentWriter::new(max7219, 0);
+/// write!(text, "{:>6.1}C", temp).unwrap();
+/// text.flush().unwrap();
+/// # }
+/// ```
+///
+pub struct SegmentWriter<'a, CONNECTOR, GLYPHS = SevenSegment, const DEVICES: usize = MAX_DISPLAYS>
+where
+    CONNECTOR: Connector,
+    GLYPHS: GlyphTable<Glyph = u8>,
+{
+    max7219: &'a mut MAX7219<CONNECTOR, DEVICES>,
+    device_addr: usize,
+    glyphs: GLYPHS,
+    buffer: SegmentBuffer,
+}
+
+impl<'a, CONNECTOR, const DEVICES: usize> SegmentWriter<'a, CONNECTOR, SevenSegment, DEVICES>
+where
+    CONNECTOR: Connector,
+{
+    ///
+    /// Creates an empty text view of the `device_addr` display, rendered with
+    /// the `SevenSegment` glyph table.
+    ///
+    pub fn new(max7219: &'a mut MAX7219<CONNECTOR, DEVICES>, device_addr: usize) -> Self {
+        SegmentWriter::with_glyphs(max7219, device_addr, SevenSegment)
+    }
+}
+
+impl<'a, CONNECTOR, GLYPHS, const DEVICES: usize> SegmentWriter<'a, CONNECTOR, GLYPHS, DEVICES>
+where
+    CONNECTOR: Connector,
+    GLYPHS: GlyphTable<Glyph = u8>,
+{
+    ///
+    /// Creates an empty text view of the `device_addr` display, rendered with
+    /// a custom glyph table.
+    ///
+    pub fn with_glyphs(
+        max7219: &'a mut MAX7219<CONNECTOR, DEVICES>,
+        device_addr: usize,
+        glyphs: GLYPHS,
+    ) -> Self {
+        SegmentWriter {
+            max7219,
+            device_addr,
+            glyphs,
+            buffer: SegmentBuffer::default(),
+        }
+    }
+
+    ///
+    /// Discards the buffered text, without writing to the display.
+    ///
+    pub fn clear(&mut self) {
+        self.buffer = SegmentBuffer::default();
+    }
+
+    ///
+    /// Writes the buffered text to the display and empties the buffer.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn flush(&mut self) -> Result<(), DriverError> {
+        self.max7219
+            .set_decode_mode(self.device_addr, DecodeMode::NoDecode)?;
+        for (position, segments) in self.buffer.segments.iter().enumerate() {
+            self.max7219
+                .write_segments(self.device_addr, position, *segments)?;
+        }
+        self.clear();
+        Ok(())
+    }
+}
+
+impl<CONNECTOR, GLYPHS, const DEVICES: usize> fmt::Write
+    for SegmentWriter<'_, CONNECTOR, GLYPHS, DEVICES>
+where
+    CONNECTOR: Connector,
+    GLYPHS: GlyphTable<Glyph = u8>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars()
+            .try_for_each(|c| self.buffer.push(c, &self.glyphs, MAX_DIGITS))
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<CONNECTOR, GLYPHS, const DEVICES: usize> ufmt_write::uWrite
+    for SegmentWriter<'_, CONNECTOR, GLYPHS, DEVICES>
+where
+    CONNECTOR: Connector,
+    GLYPHS: GlyphTable<Glyph = u8>,
+{
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        fmt::Write::write_str(self, s)
+    }
+}
+
+///
+/// Segment patterns of up to 8 characters, from left to right.
+///
+/// A `.` is folded into the decimal point of the preceding digit if it has none yet.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SegmentBuffer {
+    pub(crate) segments: [u8; MAX_DIGITS],
+    pub(crate) len: usize,
+}
+
+impl SegmentBuffer {
+    /// Adds a character, failing if it takes a digit beyond the first `capacity` ones
+    pub(crate) fn push<GLYPHS>(&mut self, c: char, glyphs: &GLYPHS, capacity: usize) -> fmt::Result
+    where
+        GLYPHS: GlyphTable<Glyph = u8>,
+    {
+        if c == '.' && self.len > 0 && self.segments[self.len - 1] & segment::DP == 0 {
+            self.segments[self.len - 1] |= segment::DP;
+            return Ok(());
+        }
+        if self.len >= capacity.min(MAX_DIGITS) {
+            return Err(fmt::Error);
+        }
+        self.segments[self.len] = glyphs.encode(c);
+        self.len += 1;
+        Ok(())
+    }
+}
+
+///
+/// Formats one line of text onto a matrix framebuffer, from its left edge on.
+///
+/// Text running past the right edge is clipped, control characters are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use core::fmt::Write;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::text::MatrixWriter;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, level: u8) {
+/// let mut text = MatrixWriter::new(max7219, Framebuffer::new(4, 1).unwrap());
+/// write!(text, "{}%", level).unwrap();
+/// text.flush().unwrap();
+/// # }
+/// ```
+///
+pub struct MatrixWriter<
+    'a,
+    CONNECTOR,
+    FONT = Font5x7,
+    const DEVICES: usize = MAX_DISPLAYS,
+    const MODULES: usize = MAX_DISPLAYS,
+> where
+    CONNECTOR: Connector,
+    FONT: Font,
+{
+    max7219: &'a mut MAX7219<CONNECTOR, DEVICES>,
+    frame: Framebuffer<MODULES>,
+    font: FONT,
+    cursor: i32,
+}
+
+impl<'a, CONNECTOR, const DEVICES: usize, const MODULES: usize>
+    MatrixWriter<'a, CONNECTOR, Font5x7, DEVICES, MODULES>
+where
+    CONNECTOR: Connector,
+{
+    ///
+    /// Creates an empty text view drawing into `frame` with the 5x7 font.
+    ///
+    pub fn new(max7219: &'a mut MAX7219<CONNECTOR, DEVICES>, frame: Framebuffer<MODULES>) -> Self {
+        MatrixWriter::with_font(max7219, frame, Font5x7)
+    }
+}
+
+impl<'a, CONNECTOR, FONT, const DEVICES: usize, const MODULES: usize>
+    MatrixWriter<'a, CONNECTOR, FONT, DEVICES, MODULES>
+where
+    CONNECTOR: Connector,
+    FONT: Font,
+{
+    ///
+    /// Creates an empty text view drawing into `frame` with a custom font.
+    ///
+    pub fn with_font(
+        max7219: &'a mut MAX7219<CONNECTOR, DEVICES>,
+        mut frame: Framebuffer<MODULES>,
+        font: FONT,
+    ) -> Self {
+        frame.clear();
+        MatrixWriter {
+            max7219,
+            frame,
+            font,
+            cursor: 0,
+        }
+    }
+
+    ///
+    /// Get the framebuffer the text is drawn into.
+    ///
+    pub fn frame(&self) -> &Framebuffer<MODULES> {
+        &self.frame
+    }
+
+    ///
+    /// Discards the buffered text, without writing to the display.
+    ///
+    pub fn clear(&mut self) {
+        self.frame.clear();
+        self.cursor = 0;
+    }
+
+    ///
+    /// Writes the buffered text to the display and empties the buffer.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn flush(&mut self) -> Result<(), DriverError> {
+        self.max7219.write_frame(&self.frame)?;
+        self.clear();
+        Ok(())
+    }
+}
+
+impl<CONNECTOR, FONT, const DEVICES: usize, const MODULES: usize> fmt::Write
+    for MatrixWriter<'_, CONNECTOR, FONT, DEVICES, MODULES>
+where
+    CONNECTOR: Connector,
+    FONT: Font,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let top = (8 - self.font.height() as i32) / 2;
+        for c in s.chars().filter(|c| !c.is_control()) {
+            if let Some(columns) = self.font.glyph(c) {
+                self.frame
+                    .draw_columns(self.cursor, top, columns, self.font.height());
+                self.cursor += columns.len() as i32 + self.font.spacing() as i32;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<CONNECTOR, FONT, const DEVICES: usize, const MODULES: usize> ufmt_write::uWrite
+    for MatrixWriter<'_, CONNECTOR, FONT, DEVICES, MODULES>
+where
+    CONNECTOR: Connector,
+    FONT: Font,
+{
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        fmt::Write::write_str(self, s)
+    }
+}
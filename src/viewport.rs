@@ -0,0 +1,236 @@
+//!
+//! Named regions of a matrix chain, each drawn in its own coordinates and at its own
+//! cadence, composited into one framebuffer.
+//!
+
+use crate::canvas::Canvas;
+use crate::connectors::traits::Connector;
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::{DriverError, RegisterAddr},
+};
+use crate::matrix::{Framebuffer, Rect};
+use crate::MAX7219;
+
+/// Maximum number of viewports a `Compositor` holds
+pub const MAX_VIEWPORTS: usize = 8;
+
+/// Refers to a viewport of a `Compositor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportId(usize);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Viewport {
+    name: &'static str,
+    area: Rect,
+    interval: u16,
+}
+
+///
+/// Splits a framebuffer into named viewports and writes it to the displays.
+///
+/// Each viewport is drawn through a `Canvas` with its origin at the viewport's top
+/// left corner and clipped to it. A viewport is due for an update every `interval`
+/// ticks, starting with the first tick. `flush` only rewrites the digit registers
+/// whose content changed since the previous flush, so updating one viewport does not
+/// rewrite the modules of the others.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::canvas::Canvas;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::{Framebuffer, Rect};
+/// # use max7219_driver::viewport::Compositor;
+/// # use max7219_driver::MAX7219;
+/// # fn draw_time(canvas: &mut Canvas) {}
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>) {
+/// let mut compositor = Compositor::new(Framebuffer::new(4, 1).unwrap());
+/// let clock = compositor.add_viewport("clock", Rect::new(0, 0, 16, 8), 100).unwrap();
+/// let ticker = compositor.add_viewport("ticker", Rect::new(16, 0, 16, 8), 1).unwrap();
+/// loop {
+///     if compositor.is_due(clock) {
+///         draw_time(&mut compositor.canvas(clock));
+///     }
+///     if compositor.is_due(ticker) {
+///         compositor.canvas(ticker).rotate(-1, 0);
+///     }
+///     compositor.flush(max7219).unwrap();
+///     compositor.tick();
+/// }
+/// # }
+/// ```
+///
+pub struct Compositor<const MODULES: usize = MAX_DISPLAYS> {
+    frame: Framebuffer<MODULES>,
+    shown: Framebuffer<MODULES>,
+    synced: bool,
+    viewports: [Viewport; MAX_VIEWPORTS],
+    count: usize,
+    ticks: u32,
+}
+
+impl<const MODULES: usize> Compositor<MODULES> {
+    ///
+    /// Creates a compositor over `frame`, without viewports.
+    /// The first flush writes every digit register.
+    ///
+    pub fn new(frame: Framebuffer<MODULES>) -> Self {
+        Compositor {
+            shown: frame.clone(),
+            frame,
+            synced: false,
+            viewports: [Viewport::default(); MAX_VIEWPORTS],
+            count: 0,
+            ticks: 0,
+        }
+    }
+
+    ///
+    /// Adds a viewport.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name to find the viewport by
+    /// * `area` - area of the framebuffer covered by the viewport
+    /// * `interval` - number of ticks between updates of the viewport
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the name is taken, the area is empty
+    ///   or not within the framebuffer, or there are already `MAX_VIEWPORTS` viewports
+    ///
+    pub fn add_viewport(
+        &mut self,
+        name: &'static str,
+        area: Rect,
+        interval: u16,
+    ) -> Result<ViewportId, DriverError> {
+        if self.find(name).is_some()
+            || area.width <= 0
+            || area.height <= 0
+            || area.intersect(self.frame.bounds()) != area
+            || self.count == MAX_VIEWPORTS
+        {
+            return Err(DriverError::InvalidValue);
+        }
+        self.viewports[self.count] = Viewport {
+            name,
+            area,
+            interval: interval.max(1),
+        };
+        self.count += 1;
+        Ok(ViewportId(self.count - 1))
+    }
+
+    ///
+    /// Get the viewport called `name`, if any.
+    ///
+    pub fn find(&self, name: &str) -> Option<ViewportId> {
+        self.viewports[..self.count]
+            .iter()
+            .position(|viewport| viewport.name == name)
+            .map(ViewportId)
+    }
+
+    ///
+    /// Get the area of the framebuffer covered by a viewport.
+    ///
+    pub fn area(&self, id: ViewportId) -> Rect {
+        self.viewport(id).area
+    }
+
+    ///
+    /// Get whether a viewport is due for an update at the current tick.
+    ///
+    pub fn is_due(&self, id: ViewportId) -> bool {
+        self.ticks
+            .is_multiple_of(self.viewport(id).interval.max(1) as u32)
+    }
+
+    ///
+    /// Get a canvas to draw into a viewport.
+    ///
+    pub fn canvas(&mut self, id: ViewportId) -> Canvas<'_, MODULES> {
+        let area = self.viewport(id).area;
+        Canvas::with_area(&mut self.frame, area)
+    }
+
+    ///
+    /// Get the composited framebuffer.
+    ///
+    pub fn frame(&self) -> &Framebuffer<MODULES> {
+        &self.frame
+    }
+
+    ///
+    /// Advances to the next tick.
+    ///
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+
+    ///
+    /// Writes the digit registers that changed since the previous flush, with one
+    /// chain write per digit row that changed on any device, the other devices
+    /// getting a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `max7219` - the driver of the chain, with the framebuffer's modules connected in order
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the framebuffer has more modules
+    ///   than there are connected displays
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn flush<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        if !self.synced {
+            max7219.write_frame(&self.frame)?;
+        } else {
+            let devices = max7219.devices();
+            if self.frame.devices() > devices {
+                return Err(DriverError::InvalidValue);
+            }
+            for digit in 0..MAX_DIGITS {
+                let register = RegisterAddr::digit(digit as u8)? as u8;
+                let mut frame = [[RegisterAddr::NoOp as u8, 0]; DEVICES];
+                let mut changed = false;
+                for device_addr in 0..self.frame.devices() {
+                    let row = self.frame.device_rows(device_addr)[digit];
+                    if row != self.shown.device_rows(device_addr)[digit] {
+                        frame[device_addr] = [register, row];
+                        changed = true;
+                    }
+                }
+                if changed {
+                    let frame = frame.as_flattened().get(..devices * 2);
+                    max7219.write_chain(frame.ok_or(DriverError::InvalidValue)?)?;
+                }
+            }
+        }
+        self.shown = self.frame.clone();
+        self.synced = true;
+        Ok(())
+    }
+
+    ///
+    /// Makes the next flush write every digit register, e.g. after the displays
+    /// were cleared or reconfigured.
+    ///
+    pub fn invalidate(&mut self) {
+        self.synced = false;
+    }
+
+    fn viewport(&self, id: ViewportId) -> &Viewport {
+        // ids are below MAX_VIEWPORTS, unused slots being empty viewports
+        &self.viewports[id.0]
+    }
+}
@@ -0,0 +1,229 @@
+//!
+//! Bar graphs and level meters for matrix displays and discrete LED bars.
+//!
+//! On a discrete LED bar each digit register drives a group of 8 LEDs: LED `n`
+//! is bit `n % 8` of digit register `n / 8`, counting from bit 0 of Digit0.
+//!
+
+use crate::connectors::traits::Connector;
+use crate::global::{consts::MAX_DIGITS, enums::DriverError};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+/// Maximum number of bars of a `BarGraph`, the width of a full 8 module chain
+pub const MAX_BARS: usize = 64;
+
+///
+/// Linear scale from a numeric range to a number of lit steps.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scale {
+    min: i32,
+    max: i32,
+}
+
+impl Scale {
+    ///
+    /// Creates a scale where `min` lights nothing and `max` lights every step.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `min` is not below `max`
+    ///
+    pub fn new(min: i32, max: i32) -> Result<Self, DriverError> {
+        if min >= max {
+            return Err(DriverError::InvalidValue);
+        }
+        Ok(Scale { min, max })
+    }
+
+    ///
+    /// Get the number of lit steps out of `steps` for `value`, rounded to the
+    /// nearest step and clamped to the range.
+    ///
+    pub fn level(&self, value: i32, steps: u32) -> u32 {
+        let value = value.clamp(self.min, self.max) as i64 - self.min as i64;
+        let span = self.max as i64 - self.min as i64;
+        ((value * steps as i64 + span / 2) / span) as u32
+    }
+}
+
+/// The direction bars grow in on a matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// One bar per column, growing from the bottom up
+    Vertical,
+    /// One bar per row, growing from left to right
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Peak {
+    level: u32,
+    hold: u16,
+}
+
+///
+/// Bar graph for matrix displays, with optional peak-hold markers.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::widgets::bar_graph::{BarGraph, Orientation, Scale};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, bands: [i32; 32]) {
+/// # let mut frame = Framebuffer::new(4, 1).unwrap();
+/// let mut meter = BarGraph::new(Scale::new(0, 1023).unwrap(), Orientation::Vertical);
+/// meter.set_peak_hold(20);
+/// loop {
+///     frame.clear();
+///     meter.draw(&mut frame, 0, 0, 32, 8, &bands);
+///     max7219.write_frame(&frame).unwrap();
+/// }
+/// # }
+/// ```
+///
+pub struct BarGraph {
+    scale: Scale,
+    orientation: Orientation,
+    peaks: [Peak; MAX_BARS],
+    hold_ticks: u16,
+}
+
+impl BarGraph {
+    ///
+    /// Creates a bar graph without peak-hold markers.
+    ///
+    pub fn new(scale: Scale, orientation: Orientation) -> Self {
+        BarGraph {
+            scale,
+            orientation,
+            peaks: [Peak::default(); MAX_BARS],
+            hold_ticks: 0,
+        }
+    }
+
+    ///
+    /// Sets how many draws a peak marker stays at its highest level before it
+    /// falls back by one step per draw. 0 disables the markers.
+    ///
+    pub fn set_peak_hold(&mut self, ticks: u16) {
+        self.hold_ticks = ticks;
+        self.peaks = [Peak::default(); MAX_BARS];
+    }
+
+    ///
+    /// Draws one bar per value into an area of the framebuffer and updates the
+    /// peak-hold markers. Values beyond the number of columns (vertical) or
+    /// rows (horizontal) of the area are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the framebuffer to draw into
+    /// * `x`, `y` - top left corner of the area
+    /// * `width`, `height` - size of the area in pixels
+    /// * `values` - the values of the bars, scaled to the length of the area
+    ///
+    pub fn draw<const MODULES: usize>(
+        &mut self,
+        frame: &mut Framebuffer<MODULES>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        values: &[i32],
+    ) {
+        let (bars, length) = match self.orientation {
+            Orientation::Vertical => (width, height),
+            Orientation::Horizontal => (height, width),
+        };
+        let length = length.max(0) as u32;
+        for (i, value) in values
+            .iter()
+            .enumerate()
+            .take(bars.clamp(0, MAX_BARS as i32) as usize)
+        {
+            let level = self.scale.level(*value, length);
+            let peak = self.update_peak(i, level);
+            for step in 0..length {
+                let on = step < level || (self.hold_ticks > 0 && step + 1 == peak);
+                let (px, py) = match self.orientation {
+                    Orientation::Vertical => (x + i as i32, y + height - 1 - step as i32),
+                    Orientation::Horizontal => (x + step as i32, y + i as i32),
+                };
+                frame.set_pixel(px, py, on);
+            }
+        }
+    }
+
+    fn update_peak(&mut self, bar: usize, level: u32) -> u32 {
+        let peak = &mut self.peaks[bar];
+        if level >= peak.level {
+            peak.level = level;
+            peak.hold = self.hold_ticks;
+        } else if peak.hold > 0 {
+            peak.hold -= 1;
+        } else {
+            peak.level -= 1;
+        }
+        peak.level
+    }
+}
+
+///
+/// Get the digit register contents lighting the first `leds` LEDs of a 64 LED bar.
+///
+pub fn led_bar_rows(leds: u32) -> [u8; MAX_DIGITS] {
+    let mut rows = [0; MAX_DIGITS];
+    for (i, row) in rows.iter_mut().enumerate() {
+        let lit = leds.saturating_sub(i as u32 * 8).min(8);
+        *row = ((1u16 << lit) - 1) as u8;
+    }
+    rows
+}
+
+///
+/// Writes `value` as a single 64 LED bar to the `device_addr` display.
+///
+/// # Errors
+///
+/// * `DriverError` - returned in case there was an error during data transfer
+///
+pub fn write_led_bar<CONNECTOR, const DEVICES: usize>(
+    max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    device_addr: usize,
+    scale: &Scale,
+    value: i32,
+) -> Result<(), DriverError>
+where
+    CONNECTOR: Connector,
+{
+    let rows = led_bar_rows(scale.level(value, (MAX_DIGITS * 8) as u32));
+    max7219.write_raw_all(device_addr, &rows)
+}
+
+///
+/// Writes eight independent 8 LED meters to the `device_addr` display,
+/// `values[n]` being shown on the LEDs of digit register `n`.
+///
+/// # Errors
+///
+/// * `DriverError` - returned in case there was an error during data transfer
+///
+pub fn write_led_segments<CONNECTOR, const DEVICES: usize>(
+    max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    device_addr: usize,
+    scale: &Scale,
+    values: &[i32; MAX_DIGITS],
+) -> Result<(), DriverError>
+where
+    CONNECTOR: Connector,
+{
+    let mut rows = [0; MAX_DIGITS];
+    for (row, value) in rows.iter_mut().zip(values.iter()) {
+        *row = led_bar_rows(scale.level(*value, 8))[0];
+    }
+    max7219.write_raw_all(device_addr, &rows)
+}
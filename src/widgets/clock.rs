@@ -0,0 +1,269 @@
+//!
+//! Clock, timer and date formatting for seven segment and matrix displays.
+//!
+//! Seven segment layouts are right aligned, so that they also fit 4 and 6 digit
+//! modules, and use decimal points as colon surrogates. Matrix layouts are drawn
+//! into a `Framebuffer`. Blinking is left to the caller through the `colon`
+//! argument, e.g. `seconds % 2 == 0` for a colon blinking at 1 Hz.
+//!
+
+use crate::connectors::traits::Connector;
+use crate::font::{Digits3x5, Font, Font5x7};
+use crate::global::{consts::MAX_DIGITS, enums::DriverError};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+///
+/// Eight characters and a dot mask, as taken by `MAX7219::write_str`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentText {
+    /// The characters from left to right
+    pub chars: [u8; MAX_DIGITS],
+    /// u8 bit array specifying where to put dots in the string (1 = dot, 0 = not)
+    pub dots: u8,
+}
+
+impl SegmentText {
+    ///
+    /// Writes the text to the `device_addr` display.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn write<CONNECTOR, const DEVICES: usize>(
+        &self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+        device_addr: usize,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        max7219.write_str(device_addr, &self.chars, self.dots)
+    }
+}
+
+/// The order of the fields of a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `DD-MM-YY`
+    DayMonthYear,
+    /// `MM-DD-YY`
+    MonthDayYear,
+    /// `YY-MM-DD`
+    YearMonthDay,
+}
+
+///
+/// Formats `HH.MM` on the four rightmost digits, the dot standing for the colon.
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if `hours` or `minutes` is out of range
+///
+pub fn hh_mm(hours: u8, minutes: u8, colon: bool) -> Result<SegmentText, DriverError> {
+    check_time(hours, minutes, 0)?;
+    let mut text = blank();
+    put_two(&mut text.chars, 4, hours);
+    put_two(&mut text.chars, 6, minutes);
+    text.dots = dot_at(5, colon);
+    Ok(text)
+}
+
+///
+/// Formats `HH.MM.SS` on the six rightmost digits, the dots standing for the colons.
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if a field is out of range
+///
+pub fn hh_mm_ss(
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+    colon: bool,
+) -> Result<SegmentText, DriverError> {
+    check_time(hours, minutes, seconds)?;
+    let mut text = blank();
+    put_two(&mut text.chars, 2, hours);
+    put_two(&mut text.chars, 4, minutes);
+    put_two(&mut text.chars, 6, seconds);
+    text.dots = dot_at(3, colon) | dot_at(5, colon);
+    Ok(text)
+}
+
+///
+/// Formats a countdown as `MM.SS.t` on the five rightmost digits, the first dot
+/// standing for the colon and the second being the decimal point.
+///
+/// # Arguments
+///
+/// * `tenths` - remaining time in tenths of a second, at most 99:59.9
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if `tenths` is above 99:59.9
+///
+pub fn mm_ss_t(tenths: u32, colon: bool) -> Result<SegmentText, DriverError> {
+    if tenths >= 100 * 60 * 10 {
+        return Err(DriverError::InvalidValue);
+    }
+    let mut text = blank();
+    put_two(&mut text.chars, 3, (tenths / 600) as u8);
+    put_two(&mut text.chars, 5, (tenths / 10 % 60) as u8);
+    text.chars[7] = b'0' + (tenths % 10) as u8;
+    text.dots = dot_at(4, colon) | dot_at(6, true);
+    Ok(text)
+}
+
+///
+/// Formats a date on all eight digits, e.g. `18-10-26`.
+///
+/// # Arguments
+///
+/// * `day` - day of the month in the range 1..=31
+/// * `month` - month in the range 1..=12
+/// * `year` - the year, only its last two digits are shown
+/// * `order` - the order of the fields
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if `day` or `month` is out of range
+///
+pub fn date(day: u8, month: u8, year: u16, order: DateOrder) -> Result<SegmentText, DriverError> {
+    if !(1..=31).contains(&day) || !(1..=12).contains(&month) {
+        return Err(DriverError::InvalidValue);
+    }
+    let year = (year % 100) as u8;
+    let fields = match order {
+        DateOrder::DayMonthYear => [day, month, year],
+        DateOrder::MonthDayYear => [month, day, year],
+        DateOrder::YearMonthDay => [year, month, day],
+    };
+    let mut text = blank();
+    for (i, field) in fields.iter().enumerate() {
+        put_two(&mut text.chars, i * 3, *field);
+    }
+    text.chars[2] = b'-';
+    text.chars[5] = b'-';
+    Ok(text)
+}
+
+///
+/// Draws `HH:MM` centered on a 32x8 area with the 5x7 font.
+///
+/// # Arguments
+///
+/// * `frame` - the framebuffer to draw into
+/// * `x` - left edge of the 32 pixel wide area
+/// * `y` - top edge of the 8 pixel high area
+/// * `colon` - whether the colon is shown
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if `hours` or `minutes` is out of range
+///
+pub fn draw_hh_mm<const MODULES: usize>(
+    frame: &mut Framebuffer<MODULES>,
+    x: i32,
+    y: i32,
+    hours: u8,
+    minutes: u8,
+    colon: bool,
+) -> Result<(), DriverError> {
+    check_time(hours, minutes, 0)?;
+    draw_fields(frame, x, y, &[hours, minutes], colon, &Font5x7);
+    Ok(())
+}
+
+///
+/// Draws `HH:MM:SS` centered on a 32x8 area with the compact 3x5 digit font.
+///
+/// # Arguments
+///
+/// * `frame` - the framebuffer to draw into
+/// * `x` - left edge of the 32 pixel wide area
+/// * `y` - top edge of the 8 pixel high area
+/// * `colon` - whether the colons are shown
+///
+/// # Errors
+///
+/// * `DriverError::InvalidValue` - returned if a field is out of range
+///
+pub fn draw_hh_mm_ss<const MODULES: usize>(
+    frame: &mut Framebuffer<MODULES>,
+    x: i32,
+    y: i32,
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+    colon: bool,
+) -> Result<(), DriverError> {
+    check_time(hours, minutes, seconds)?;
+    draw_fields(frame, x, y, &[hours, minutes, seconds], colon, &Digits3x5);
+    Ok(())
+}
+
+// draws two digit fields separated by colons, centered on a 32x8 area
+fn draw_fields<F: Font, const MODULES: usize>(
+    frame: &mut Framebuffer<MODULES>,
+    x: i32,
+    y: i32,
+    fields: &[u8],
+    colon: bool,
+    font: &F,
+) {
+    let mut text = [b' '; 8];
+    let mut len = 0;
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            text[len] = b':';
+            len += 1;
+        }
+        put_two(&mut text, len, *field);
+        len += 2;
+    }
+    let text = core::str::from_utf8(&text[..len]).unwrap_or_default();
+
+    // measure with the colons, so that the digits do not move when they blink
+    let width = font.text_width(text);
+    let left = x + (32 - width) / 2;
+    let top = y + (8 - font.height() as i32) / 2;
+
+    let mut cursor = left;
+    for c in text.chars() {
+        let columns = font.glyph(c).unwrap_or(&[]);
+        if c != ':' || colon {
+            frame.draw_columns(cursor, top, columns, font.height());
+        }
+        cursor += columns.len() as i32 + font.spacing() as i32;
+    }
+}
+
+fn check_time(hours: u8, minutes: u8, seconds: u8) -> Result<(), DriverError> {
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return Err(DriverError::InvalidValue);
+    }
+    Ok(())
+}
+
+fn blank() -> SegmentText {
+    SegmentText {
+        chars: [b' '; MAX_DIGITS],
+        dots: 0,
+    }
+}
+
+fn put_two(chars: &mut [u8], position: usize, value: u8) {
+    chars[position] = b'0' + value / 10 % 10;
+    chars[position + 1] = b'0' + value % 10;
+}
+
+fn dot_at(position: usize, on: bool) -> u8 {
+    if on {
+        0b1000_0000 >> position
+    } else {
+        0
+    }
+}
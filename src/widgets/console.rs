@@ -0,0 +1,219 @@
+//!
+//! A scrolling text console for matrix arrays, e.g. a log output on headless devices.
+//!
+
+use core::fmt;
+
+use crate::connectors::traits::Connector;
+use crate::font::{Font, Font5x7};
+use crate::global::{consts::MAX_DISPLAYS, enums::DriverError};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+///
+/// A terminal-style text console drawn into a framebuffer.
+///
+/// Text is written at the cursor and wraps onto the next line when it would run past
+/// the right edge. `\n` starts a new line and `\r` returns to the start of the current
+/// one; other control characters are ignored. Starting a new line below the last one
+/// scrolls the whole console up by one line. A `\n` only takes effect once the next
+/// character is written, so text ending in a line break stays on the last line.
+///
+/// Writing only updates the framebuffer, `flush` sends it to the displays.
+///
+/// # Examples
+///
+/// ```
+/// # use core::fmt::Write;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::widgets::console::TextConsole;
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, version: &str) {
+/// // two 32x16 panels stacked on top of each other
+/// let mut console = TextConsole::new(Framebuffer::new(4, 2).unwrap()).unwrap();
+/// writeln!(console, "boot {}", version).unwrap();
+/// console.flush(max7219).unwrap();
+/// # }
+/// ```
+///
+pub struct TextConsole<FONT = Font5x7, const MODULES: usize = MAX_DISPLAYS> {
+    frame: Framebuffer<MODULES>,
+    font: FONT,
+    x: i32,
+    line: i32,
+    // a '\n' that was written but not yet carried out
+    pending_newline: bool,
+}
+
+impl<const MODULES: usize> TextConsole<Font5x7, MODULES> {
+    ///
+    /// Creates an empty console over `frame`, using the 5x7 font.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `frame` is not high enough for one line
+    ///
+    pub fn new(frame: Framebuffer<MODULES>) -> Result<Self, DriverError> {
+        TextConsole::with_font(frame, Font5x7)
+    }
+}
+
+impl<FONT: Font, const MODULES: usize> TextConsole<FONT, MODULES> {
+    ///
+    /// Creates an empty console over `frame`, using a custom font.
+    /// Lines are one pixel higher than the font.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `frame` is not high enough for one line
+    ///
+    pub fn with_font(mut frame: Framebuffer<MODULES>, font: FONT) -> Result<Self, DriverError> {
+        if frame.height() < font.height() as i32 {
+            return Err(DriverError::InvalidValue);
+        }
+        frame.clear();
+        Ok(TextConsole {
+            frame,
+            font,
+            x: 0,
+            line: 0,
+            pending_newline: false,
+        })
+    }
+
+    ///
+    /// Get the number of text lines the console shows.
+    ///
+    pub fn lines(&self) -> i32 {
+        // the last line does not need the spacing row below it
+        (self.frame.height() + 1) / self.line_height()
+    }
+
+    ///
+    /// Get the cursor position as the pixel column and the line index.
+    ///
+    pub fn cursor(&self) -> (i32, i32) {
+        (self.x, self.line)
+    }
+
+    ///
+    /// Get the framebuffer the console is drawn into.
+    ///
+    pub fn frame(&self) -> &Framebuffer<MODULES> {
+        &self.frame
+    }
+
+    ///
+    /// Clears the console and moves the cursor to the top left.
+    ///
+    pub fn clear(&mut self) {
+        self.frame.clear();
+        self.x = 0;
+        self.line = 0;
+        self.pending_newline = false;
+    }
+
+    ///
+    /// Moves the cursor to the start of the next line, scrolling up if the cursor
+    /// is on the last line.
+    ///
+    pub fn newline(&mut self) {
+        self.x = 0;
+        if self.line + 1 < self.lines() {
+            self.line += 1;
+        } else {
+            self.scroll_up();
+        }
+    }
+
+    ///
+    /// Scrolls the content up by one line, blanking the last line.
+    /// The cursor is left where it is.
+    ///
+    pub fn scroll_up(&mut self) {
+        let shift = self.line_height();
+        for y in 0..self.frame.height() {
+            for x in 0..self.frame.width() {
+                let on = self.frame.pixel(x, y + shift);
+                self.frame.set_pixel(x, y, on);
+            }
+        }
+    }
+
+    ///
+    /// Writes the console to the displays.
+    ///
+    /// # Arguments
+    ///
+    /// * `max7219` - the driver of the chain, with the framebuffer's modules connected in order
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn flush<CONNECTOR: Connector, const DEVICES: usize>(
+        &self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError> {
+        max7219.write_frame(&self.frame)
+    }
+
+    fn line_height(&self) -> i32 {
+        self.font.height() as i32 + 1
+    }
+
+    fn put(&mut self, c: char) {
+        match c {
+            '\n' => {
+                if self.pending_newline {
+                    self.newline();
+                }
+                self.pending_newline = true;
+            }
+            '\r' => self.x = 0,
+            c if c.is_control() => {}
+            c => {
+                let Some(width) = self.font.glyph(c).map(|columns| columns.len() as i32) else {
+                    return;
+                };
+                if self.pending_newline {
+                    self.pending_newline = false;
+                    self.newline();
+                }
+                if self.x > 0 && self.x + width > self.frame.width() {
+                    self.newline();
+                }
+                let y = self.line * self.line_height();
+                // clear the whole cell first, as text after a '\r' is drawn over older text
+                let cell = width + self.font.spacing() as i32;
+                for dy in 0..self.line_height() {
+                    for dx in 0..cell {
+                        self.frame.set_pixel(self.x + dx, y + dy, false);
+                    }
+                }
+                if let Some(columns) = self.font.glyph(c) {
+                    self.frame
+                        .draw_columns(self.x, y, columns, self.font.height());
+                }
+                self.x += width + self.font.spacing() as i32;
+            }
+        }
+    }
+}
+
+impl<FONT: Font, const MODULES: usize> fmt::Write for TextConsole<FONT, MODULES> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().for_each(|c| self.put(c));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<FONT: Font, const MODULES: usize> ufmt_write::uWrite for TextConsole<FONT, MODULES> {
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        fmt::Write::write_str(self, s)
+    }
+}
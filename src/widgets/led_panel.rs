@@ -0,0 +1,247 @@
+//!
+//! Discrete indicator LEDs addressed by index instead of register and bit.
+//!
+
+use crate::connectors::traits::Connector;
+use crate::global::{
+    consts::{MAX_DIGITS, MAX_DISPLAYS},
+    enums::{DecodeMode, DriverError, RegisterAddr},
+};
+use crate::MAX7219;
+
+///
+/// The position of one LED in the chain.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedLocation {
+    /// display as connected in series (0 -> last)
+    pub device_addr: usize,
+    /// digit register index in the range 0..=7 (0 = Digit0)
+    pub digit: u8,
+    /// segment bit in the range 0..=7 (7 = DP)
+    pub bit: u8,
+}
+
+///
+/// Maps logical LED indices to their position in the chain.
+///
+pub trait LedMap {
+    ///
+    /// Get the position of the LED `led`, or `None` if there is no such LED.
+    ///
+    fn locate(&self, led: usize) -> Option<LedLocation>;
+}
+
+///
+/// The identity mapping: LED `n` is bit `n % 8` of digit `n / 8 % 8` of device `n / 64`.
+/// The LEDs past the capacity of the panel are rejected by the panel.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearMap;
+
+impl LedMap for LinearMap {
+    fn locate(&self, led: usize) -> Option<LedLocation> {
+        Some(LedLocation {
+            device_addr: led / (MAX_DIGITS * 8),
+            digit: (led / 8 % MAX_DIGITS) as u8,
+            bit: (led % 8) as u8,
+        })
+    }
+}
+
+/// A lookup table, entry `n` being the position of LED `n`
+impl LedMap for &[LedLocation] {
+    fn locate(&self, led: usize) -> Option<LedLocation> {
+        self.get(led).copied()
+    }
+}
+
+///
+/// A panel of discrete LEDs driven by a chain of up to `DEVICES` displays, as many
+/// as the driver it is written through.
+///
+/// Changes are only kept in memory until `flush`, which writes the digit
+/// registers that changed since the last flush. The LEDs are driven as raw
+/// segment bits, so the first flush to each display also sets it to
+/// `DecodeMode::NoDecode`.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::widgets::led_panel::{LedPanel, LinearMap};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>) {
+/// const POWER: usize = 0;
+/// const LINK: usize = 1;
+/// const ERRORS: [usize; 3] = [8, 9, 10];
+///
+/// let mut panel = LedPanel::new(LinearMap);
+/// panel.set_led(POWER, true).unwrap();
+/// panel.set_group(&ERRORS, false).unwrap();
+/// panel.flush(max7219).unwrap();
+/// # }
+/// ```
+///
+pub struct LedPanel<MAP, const DEVICES: usize = MAX_DISPLAYS> {
+    map: MAP,
+    rows: [[u8; MAX_DIGITS]; DEVICES],
+    // bit n set if digit n of the device changed since the last flush
+    dirty: [u8; DEVICES],
+    // whether `NoDecode` was written to the display
+    decode_set: [bool; DEVICES],
+}
+
+impl<MAP> LedPanel<MAP>
+where
+    MAP: LedMap,
+{
+    ///
+    /// Creates a panel of up to `MAX_DISPLAYS` displays with every LED off,
+    /// which is written in full on the first flush.
+    ///
+    pub fn new(map: MAP) -> Self {
+        LedPanel::with_capacity(map)
+    }
+}
+
+impl<MAP, const DEVICES: usize> LedPanel<MAP, DEVICES>
+where
+    MAP: LedMap,
+{
+    ///
+    /// Creates a panel of up to `DEVICES` displays with every LED off,
+    /// which is written in full on the first flush.
+    ///
+    pub fn with_capacity(map: MAP) -> Self {
+        LedPanel {
+            map,
+            rows: [[0; MAX_DIGITS]; DEVICES],
+            dirty: [0xFF; DEVICES],
+            decode_set: [false; DEVICES],
+        }
+    }
+
+    ///
+    /// Get the state of an LED as it will be after the next flush.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the map has no such LED
+    ///
+    pub fn led(&self, led: usize) -> Result<bool, DriverError> {
+        let location = self.locate(led)?;
+        Ok(self.rows[location.device_addr][location.digit as usize] & (1 << location.bit) != 0)
+    }
+
+    ///
+    /// Turns an LED on or off.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the map has no such LED
+    ///
+    pub fn set_led(&mut self, led: usize, on: bool) -> Result<(), DriverError> {
+        let location = self.locate(led)?;
+        let row = &mut self.rows[location.device_addr][location.digit as usize];
+        let updated = if on {
+            *row | (1 << location.bit)
+        } else {
+            *row & !(1 << location.bit)
+        };
+        if updated != *row {
+            *row = updated;
+            self.dirty[location.device_addr] |= 1 << location.digit;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Toggles an LED.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the map has no such LED
+    ///
+    pub fn toggle_led(&mut self, led: usize) -> Result<(), DriverError> {
+        let on = self.led(led)?;
+        self.set_led(led, !on)
+    }
+
+    ///
+    /// Turns a group of LEDs on or off. Nothing is changed if one of them is not mapped.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the map has no such LED
+    ///
+    pub fn set_group(&mut self, leds: &[usize], on: bool) -> Result<(), DriverError> {
+        for led in leds {
+            self.locate(*led)?;
+        }
+        for led in leds {
+            self.set_led(*led, on)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Turns every LED off.
+    ///
+    pub fn clear(&mut self) {
+        for (rows, dirty) in self.rows.iter_mut().zip(self.dirty.iter_mut()) {
+            for (digit, row) in rows.iter_mut().enumerate() {
+                if *row != 0 {
+                    *row = 0;
+                    *dirty |= 1 << digit;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Writes the digit registers changed since the last flush, along with the
+    /// decode mode of the displays not flushed to before.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if an LED is on on a display past the
+    ///   end of the chain
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn flush<CONNECTOR>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        let devices = max7219.devices().min(DEVICES);
+        if self.rows[devices..].iter().flatten().any(|row| *row != 0) {
+            return Err(DriverError::InvalidValue);
+        }
+        for device_addr in 0..devices {
+            if self.dirty[device_addr] != 0 && !self.decode_set[device_addr] {
+                max7219.set_decode_mode(device_addr, DecodeMode::NoDecode)?;
+                self.decode_set[device_addr] = true;
+            }
+            for digit in 0..MAX_DIGITS as u8 {
+                if self.dirty[device_addr] & (1 << digit) != 0 {
+                    let data = self.rows[device_addr][digit as usize];
+                    max7219.write_raw(device_addr, RegisterAddr::digit(digit)?, data)?;
+                    self.dirty[device_addr] &= !(1 << digit);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn locate(&self, led: usize) -> Result<LedLocation, DriverError> {
+        match self.map.locate(led) {
+            Some(l) if l.device_addr < DEVICES && (l.digit as usize) < MAX_DIGITS && l.bit < 8 => {
+                Ok(l)
+            }
+            _ => Err(DriverError::InvalidValue),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod bar_graph;
+pub mod clock;
+pub mod console;
+pub mod led_panel;
+pub mod text_effects;
+pub mod transition;
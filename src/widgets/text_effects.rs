@@ -0,0 +1,160 @@
+//!
+//! Animated per-character effects for matrix text, applied to spans of a string.
+//!
+
+use core::ops::Range;
+
+use crate::font::Font;
+use crate::global::enums::DriverError;
+use crate::layout::{CharStyle, TextLayout};
+use crate::matrix::{Framebuffer, Rect};
+
+/// Maximum number of spans a `TextEffects` holds
+pub const MAX_SPANS: usize = 8;
+
+/// An animation applied to the characters of a span
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEffect {
+    /// The characters appear one after the other, one every `ticks`
+    Typewriter { ticks: u16 },
+    /// The characters alternate between shown and hidden every `ticks`
+    Blink { ticks: u16 },
+    /// The characters alternate between inverted and normal every `ticks`
+    Flash { ticks: u16 },
+    /// The characters are always drawn dark on a lit background
+    Inverted,
+    /// The characters hop up to `height` pixels in a wave, moving one pixel every `ticks`
+    Bounce { height: u8, ticks: u16 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Span {
+    chars: Range<usize>,
+    effect: TextEffect,
+}
+
+///
+/// Effects applied to spans of a string drawn with a `TextLayout`.
+///
+/// Spans are given as ranges of character indices, so they work the same with any
+/// font, alignment and wrapping. Where spans overlap their effects are combined.
+/// The animation advances with `tick`, the text is then redrawn with `draw`.
+///
+/// # Examples
+///
+/// ```
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::layout::TextLayout;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::widgets::text_effects::{TextEffect, TextEffects};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(max7219: &mut MAX7219<C>, layout: TextLayout) {
+/// # let mut frame = Framebuffer::new(4, 1).unwrap();
+/// # let area = frame.bounds();
+/// // "Temp: 21C", the value blinking
+/// let mut effects = TextEffects::new();
+/// effects.add_span(6..9, TextEffect::Blink { ticks: 10 }).unwrap();
+/// loop {
+///     frame.clear();
+///     effects.draw(&layout, &mut frame, area, "Temp: 21C");
+///     max7219.write_frame(&frame).unwrap();
+///     effects.tick();
+/// }
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct TextEffects {
+    spans: [Option<Span>; MAX_SPANS],
+    ticks: u32,
+}
+
+impl TextEffects {
+    ///
+    /// Creates an empty set of effects, at the start of the animation.
+    ///
+    pub fn new() -> Self {
+        TextEffects::default()
+    }
+
+    ///
+    /// Applies `effect` to the characters with an index in `chars`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if there are already `MAX_SPANS` spans
+    ///
+    pub fn add_span(&mut self, chars: Range<usize>, effect: TextEffect) -> Result<(), DriverError> {
+        let free = self
+            .spans
+            .iter_mut()
+            .find(|span| span.is_none())
+            .ok_or(DriverError::InvalidValue)?;
+        *free = Some(Span { chars, effect });
+        Ok(())
+    }
+
+    ///
+    /// Removes every span.
+    ///
+    pub fn clear_spans(&mut self) {
+        self.spans = Default::default();
+    }
+
+    ///
+    /// Restarts the animation, e.g. to type the text out again.
+    ///
+    pub fn restart(&mut self) {
+        self.ticks = 0;
+    }
+
+    ///
+    /// Advances the animation by one tick.
+    ///
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+
+    ///
+    /// Get the style of the character at `index` at the current tick.
+    ///
+    pub fn style(&self, index: usize) -> CharStyle {
+        let mut style = CharStyle::default();
+        for span in self.spans.iter().flatten() {
+            if !span.chars.contains(&index) {
+                continue;
+            }
+            let position = (index - span.chars.start) as u32;
+            let phase = |ticks: u16| self.ticks / ticks.max(1) as u32;
+            match span.effect {
+                TextEffect::Typewriter { ticks } => style.hidden |= phase(ticks) < position,
+                TextEffect::Blink { ticks } => style.hidden |= phase(ticks) % 2 == 1,
+                TextEffect::Flash { ticks } => style.inverted |= phase(ticks) % 2 == 0,
+                TextEffect::Inverted => style.inverted = true,
+                TextEffect::Bounce { height, ticks } if height > 0 => {
+                    // triangle wave, each character one pixel ahead of the previous one
+                    let period = 2 * height as u32;
+                    let step = (phase(ticks) + period - position % period) % period;
+                    style.offset_y -= step.min(period - step) as i32;
+                }
+                TextEffect::Bounce { .. } => {}
+            }
+        }
+        style
+    }
+
+    ///
+    /// Draws `text` with the effects at the current tick, using `layout`.
+    ///
+    /// Returns the number of lines drawn.
+    ///
+    pub fn draw<FONT: Font, const MODULES: usize>(
+        &self,
+        layout: &TextLayout<FONT>,
+        frame: &mut Framebuffer<MODULES>,
+        area: Rect,
+        text: &str,
+    ) -> usize {
+        layout.draw_styled(frame, area, text, |index| self.style(index))
+    }
+}
@@ -0,0 +1,281 @@
+//!
+//! Animated transitions between two matrix frames, e.g. to switch between signage pages.
+//!
+
+use embedded_hal::blocking::delay::DelayMs;
+
+use crate::connectors::traits::Connector;
+use crate::global::{
+    consts::MAX_DISPLAYS,
+    enums::{DriverError, Intensity},
+};
+use crate::matrix::Framebuffer;
+use crate::MAX7219;
+
+/// The direction content moves in during a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// How a transition goes from one frame to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Both frames move together, the new one pushing the old one out
+    Slide(Direction),
+    /// An edge moves across the displays, revealing the new frame behind it
+    Wipe(Direction),
+    /// The new frame is revealed pixel by pixel in a pseudo-random order
+    Dissolve,
+    /// The old frame scrolls out at the top, then the new one scrolls in from the bottom
+    ScrollUp,
+    /// The new frame is revealed from the middle out to both sides
+    Curtain,
+    /// The old frame dims down, then the new one brightens up to the given intensity
+    Fade(Intensity),
+}
+
+/// Number of steps of `Effect::Dissolve`
+const DISSOLVE_STEPS: u16 = 16;
+
+///
+/// A transition between two frames of the same size, shown one step per `tick`.
+///
+/// Each step writes a whole frame through `MAX7219::write_frame`, `Effect::Fade`
+/// also sets the intensity of every device.
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_hal::blocking::delay::DelayMs;
+/// # use max7219_driver::connectors::traits::Connector;
+/// # use max7219_driver::matrix::Framebuffer;
+/// # use max7219_driver::widgets::transition::{Direction, Effect, Transition};
+/// # use max7219_driver::MAX7219;
+/// # fn example<C: Connector>(
+/// #     max7219: &mut MAX7219<C>,
+/// #     delay: &mut impl DelayMs<u32>,
+/// #     page: Framebuffer,
+/// #     next_page: Framebuffer,
+/// # ) {
+/// let mut transition = Transition::new(page, next_page, Effect::Slide(Direction::Left)).unwrap();
+/// while transition.tick(max7219).unwrap() {
+///     delay.delay_ms(30u32);
+/// }
+/// # }
+/// ```
+///
+pub struct Transition<const MODULES: usize = MAX_DISPLAYS> {
+    from: Framebuffer<MODULES>,
+    to: Framebuffer<MODULES>,
+    frame: Framebuffer<MODULES>,
+    effect: Effect,
+    step: u16,
+}
+
+impl<const MODULES: usize> Transition<MODULES> {
+    ///
+    /// Creates a transition from the frame `from` to the frame `to`, not started yet.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if the frames are not the same size
+    ///
+    pub fn new(
+        from: Framebuffer<MODULES>,
+        to: Framebuffer<MODULES>,
+        effect: Effect,
+    ) -> Result<Self, DriverError> {
+        if from.width() != to.width() || from.height() != to.height() {
+            return Err(DriverError::InvalidValue);
+        }
+        Ok(Transition {
+            frame: from.clone(),
+            from,
+            to,
+            effect,
+            step: 0,
+        })
+    }
+
+    ///
+    /// Get the number of steps the transition takes.
+    ///
+    pub fn steps(&self) -> u16 {
+        let (width, height) = (self.to.width() as u16, self.to.height() as u16);
+        match self.effect {
+            Effect::Slide(Direction::Left | Direction::Right)
+            | Effect::Wipe(Direction::Left | Direction::Right) => width,
+            Effect::Slide(Direction::Up | Direction::Down)
+            | Effect::Wipe(Direction::Up | Direction::Down) => height,
+            Effect::Dissolve => DISSOLVE_STEPS,
+            Effect::ScrollUp => 2 * height,
+            Effect::Curtain => width.div_ceil(2),
+            Effect::Fade(intensity) => 2 * (u8::from(intensity) as u16 + 1),
+        }
+    }
+
+    ///
+    /// Get whether the last step has been shown.
+    ///
+    pub fn is_finished(&self) -> bool {
+        self.step >= self.steps()
+    }
+
+    ///
+    /// Get the frame shown by the last step.
+    ///
+    pub fn frame(&self) -> &Framebuffer<MODULES> {
+        &self.frame
+    }
+
+    ///
+    /// Shows the next step of the transition.
+    ///
+    /// Returns `true` while there are steps left to show, `false` once the new frame is
+    /// fully shown.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn tick<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<bool, DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        if !self.is_finished() {
+            self.step += 1;
+            self.show(max7219)?;
+        }
+        Ok(!self.is_finished())
+    }
+
+    ///
+    /// Skips to the end of the transition, showing the new frame.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn finish<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        self.step = self.steps();
+        self.show(max7219)
+    }
+
+    ///
+    /// Shows every remaining step, waiting `step_ms` milliseconds after each one.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError` - returned in case there was an error during data transfer
+    ///
+    pub fn run<CONNECTOR, DELAY, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+        delay: &mut DELAY,
+        step_ms: u32,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+        DELAY: DelayMs<u32>,
+    {
+        while self.tick(max7219)? {
+            delay.delay_ms(step_ms);
+        }
+        Ok(())
+    }
+
+    fn show<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        max7219: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> Result<(), DriverError>
+    where
+        CONNECTOR: Connector,
+    {
+        if let Effect::Fade(intensity) = self.effect {
+            // steps 1..=level + 1 dim the old frame down to 0, the rest brighten the new one
+            let level = u8::from(intensity) as u16;
+            let (frame, level) = match self.step.checked_sub(level + 2) {
+                None => (&self.from, level + 1 - self.step.min(level + 1)),
+                Some(level) => (&self.to, level),
+            };
+            max7219.set_intensity_all(Intensity::try_from(level as u8)?)?;
+            return max7219.write_frame(frame);
+        }
+        for y in 0..self.frame.height() {
+            for x in 0..self.frame.width() {
+                let on = self.compose(x, y);
+                self.frame.set_pixel(x, y, on);
+            }
+        }
+        max7219.write_frame(&self.frame)
+    }
+
+    /// Get the pixel at `(x, y)` in the current step
+    fn compose(&self, x: i32, y: i32) -> bool {
+        let (width, height) = (self.to.width(), self.to.height());
+        let step = self.step as i32;
+        let (from, to) = (&self.from, &self.to);
+        match self.effect {
+            Effect::Slide(Direction::Left) if x + step < width => from.pixel(x + step, y),
+            Effect::Slide(Direction::Left) => to.pixel(x + step - width, y),
+            Effect::Slide(Direction::Right) if x >= step => from.pixel(x - step, y),
+            Effect::Slide(Direction::Right) => to.pixel(x - step + width, y),
+            Effect::Slide(Direction::Up) if y + step < height => from.pixel(x, y + step),
+            Effect::Slide(Direction::Up) => to.pixel(x, y + step - height),
+            Effect::Slide(Direction::Down) if y >= step => from.pixel(x, y - step),
+            Effect::Slide(Direction::Down) => to.pixel(x, y - step + height),
+            Effect::Wipe(direction) => {
+                let revealed = match direction {
+                    Direction::Left => x >= width - step,
+                    Direction::Right => x < step,
+                    Direction::Up => y >= height - step,
+                    Direction::Down => y < step,
+                };
+                if revealed {
+                    to.pixel(x, y)
+                } else {
+                    from.pixel(x, y)
+                }
+            }
+            Effect::Dissolve => {
+                let index = (y * width + x) as u32;
+                if scramble(index) % (DISSOLVE_STEPS as u32) < step as u32 {
+                    to.pixel(x, y)
+                } else {
+                    from.pixel(x, y)
+                }
+            }
+            Effect::ScrollUp if step <= height => from.pixel(x, y + step),
+            Effect::ScrollUp => to.pixel(x, y + 2 * height - step),
+            Effect::Curtain => {
+                if (2 * x + 1 - width).abs() < 2 * step {
+                    to.pixel(x, y)
+                } else {
+                    from.pixel(x, y)
+                }
+            }
+            Effect::Fade(_) => to.pixel(x, y),
+        }
+    }
+}
+
+/// A cheap integer hash spreading pixel indices evenly over the dissolve steps
+fn scramble(index: u32) -> u32 {
+    let mut value = index.wrapping_mul(0x9E37_79B9) ^ 0x5BD1_E995;
+    value ^= value >> 15;
+    value = value.wrapping_mul(0x2C1B_3C6D);
+    value ^ (value >> 12)
+}
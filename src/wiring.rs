@@ -0,0 +1,98 @@
+use crate::global::{consts::MAX_DIGITS, enums::DriverError};
+
+///
+/// Describes how the digits and segments of a seven segment board are wired
+/// to the MAX7219, so that text is rendered in the right place on any board.
+///
+/// Digit positions are counted from the leftmost character (0) to the rightmost (7).
+/// Segment bits use the `DP A B C D E F G` order of `glyphs::segment`, bit 7 being `DP`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wiring {
+    // digit register index (0 = Digit0) for each character position
+    digits: [u8; MAX_DIGITS],
+    // physical bit for each logical segment bit
+    segments: [u8; 8],
+}
+
+impl Default for Wiring {
+    fn default() -> Self {
+        Wiring::STANDARD
+    }
+}
+
+impl Wiring {
+    /// The standard wiring: Digit7 is the leftmost character, segments in `DP A B C D E F G` order.
+    pub const STANDARD: Wiring = Wiring {
+        digits: [7, 6, 5, 4, 3, 2, 1, 0],
+        segments: [0, 1, 2, 3, 4, 5, 6, 7],
+    };
+
+    /// Digit0 is the leftmost character, segments in standard order.
+    pub const REVERSED: Wiring = Wiring {
+        digits: [0, 1, 2, 3, 4, 5, 6, 7],
+        segments: [0, 1, 2, 3, 4, 5, 6, 7],
+    };
+
+    ///
+    /// Set the digit register (0 = Digit0) driving each character position,
+    /// from the leftmost character to the rightmost.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `digits` is not a permutation of 0..=7
+    ///
+    pub fn with_digit_order(mut self, digits: [u8; MAX_DIGITS]) -> Result<Self, DriverError> {
+        check_permutation(&digits)?;
+        self.digits = digits;
+        Ok(self)
+    }
+
+    ///
+    /// Set the physical segment bit each logical segment is wired to, indexed by
+    /// logical bit: `segments[7]` is where `DP` is wired, `segments[0]` where `G` is.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `segments` is not a permutation of 0..=7
+    ///
+    pub fn with_segment_order(mut self, segments: [u8; 8]) -> Result<Self, DriverError> {
+        check_permutation(&segments)?;
+        self.segments = segments;
+        Ok(self)
+    }
+
+    ///
+    /// Get the digit register address (1 = Digit0) for the character `position`.
+    ///
+    /// # Errors
+    ///
+    /// * `DriverError::InvalidValue` - returned if `position` is not in the range 0..=7
+    ///
+    pub fn digit_register(&self, position: usize) -> Result<u8, DriverError> {
+        self.digits
+            .get(position)
+            .map(|digit| digit + 1)
+            .ok_or(DriverError::InvalidValue)
+    }
+
+    ///
+    /// Translate a segment pattern in standard order into the physical wiring.
+    ///
+    pub fn map_segments(&self, segments: u8) -> u8 {
+        (0..8)
+            .filter(|bit| segments & (1 << bit) != 0)
+            .fold(0, |acc, bit| acc | (1 << self.segments[bit]))
+    }
+}
+
+fn check_permutation(values: &[u8; 8]) -> Result<(), DriverError> {
+    let mut seen = 0u8;
+    for v in values {
+        if *v > 7 || seen & (1 << v) != 0 {
+            return Err(DriverError::InvalidValue);
+        }
+        seen |= 1 << v;
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::attributes::{Attribute, AttributedText};
+use max7219_driver::global::enums::RegisterAddr;
+use max7219_driver::MAX7219;
+
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+#[test]
+fn flush_writes_only_owned_digits() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let mut text = AttributedText::new();
+    text.set_segments(1, 0, 0b0011_0000).unwrap();
+    text.set_segments(1, 1, 0b0110_1101).unwrap();
+    spi.clear();
+
+    text.tick(&mut max7219).unwrap();
+    // positions 0 and 1 are digit registers 8 and 7 with the standard wiring
+    assert_eq!(
+        spi.registers(),
+        [
+            (1, DECODE_MODE, 0x00),
+            (1, 8, 0b0011_0000),
+            (1, 7, 0b0110_1101)
+        ]
+    );
+
+    spi.clear();
+    text.tick(&mut max7219).unwrap();
+    assert!(spi.registers().is_empty());
+
+    text.render(&mut max7219).unwrap();
+    assert_eq!(spi.registers().len(), 3);
+}
+
+#[test]
+fn blink_phase_repeats() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = AttributedText::new();
+    text.set_blink_ticks(2);
+    text.set_segments(0, 0, 0xFF).unwrap();
+    text.set_attribute(0, 0, Attribute::BLINK).unwrap();
+    text.render(&mut max7219).unwrap();
+
+    let mut shown = Vec::new();
+    for _ in 0..8 {
+        spi.clear();
+        text.tick(&mut max7219).unwrap();
+        shown.push(spi.registers().first().map(|(_, _, data)| *data));
+    }
+    let (on, off) = (Some(0xFF), Some(0x00));
+    assert_eq!(shown, [None, off, None, on, None, off, None, on]);
+}
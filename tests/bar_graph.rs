@@ -0,0 +1,76 @@
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::bar_graph::{led_bar_rows, BarGraph, Orientation, Scale};
+
+#[test]
+fn levels_round_to_the_nearest_step_and_clamp() {
+    let scale = Scale::new(0, 100).unwrap();
+    assert_eq!(scale.level(0, 8), 0);
+    assert_eq!(scale.level(-5, 8), 0);
+    // 0.48 and 0.56 steps
+    assert_eq!(scale.level(6, 8), 0);
+    assert_eq!(scale.level(7, 8), 1);
+    // 7.44 and 7.52 steps
+    assert_eq!(scale.level(93, 8), 7);
+    assert_eq!(scale.level(94, 8), 8);
+    assert_eq!(scale.level(100, 8), 8);
+    assert_eq!(scale.level(200, 8), 8);
+
+    let scale = Scale::new(-10, 10).unwrap();
+    assert_eq!(scale.level(0, 8), 4);
+    assert!(Scale::new(1, 1).is_err());
+}
+
+fn column(frame: &Framebuffer, x: i32) -> Vec<bool> {
+    (0..8).map(|y| frame.pixel(x, y)).collect()
+}
+
+#[test]
+fn vertical_bars_grow_from_the_bottom() {
+    let mut graph = BarGraph::new(Scale::new(0, 100).unwrap(), Orientation::Vertical);
+    let mut frame = Framebuffer::new(1, 1).unwrap();
+    graph.draw(&mut frame, 0, 0, 8, 8, &[0, 100, 50]);
+    assert_eq!(column(&frame, 0), [false; 8]);
+    assert_eq!(column(&frame, 1), [true; 8]);
+    assert_eq!(
+        column(&frame, 2),
+        [false, false, false, false, true, true, true, true]
+    );
+}
+
+#[test]
+fn horizontal_bars_grow_from_the_left() {
+    let mut graph = BarGraph::new(Scale::new(0, 100).unwrap(), Orientation::Horizontal);
+    let mut frame = Framebuffer::new(1, 1).unwrap();
+    graph.draw(&mut frame, 0, 0, 8, 8, &[0, 100, 25]);
+    let row = |y: i32| -> Vec<bool> { (0..8).map(|x| frame.pixel(x, y)).collect() };
+    assert_eq!(row(0), [false; 8]);
+    assert_eq!(row(1), [true; 8]);
+    assert_eq!(
+        row(2),
+        [true, true, false, false, false, false, false, false]
+    );
+}
+
+#[test]
+fn peak_marker_holds_then_falls_one_step_per_draw() {
+    let mut graph = BarGraph::new(Scale::new(0, 8).unwrap(), Orientation::Vertical);
+    graph.set_peak_hold(2);
+    let mut frame = Framebuffer::new(1, 1).unwrap();
+    graph.draw(&mut frame, 0, 0, 1, 8, &[6]);
+
+    // the peak at step 6 (row 2) is held for two draws
+    let mut peaks = Vec::new();
+    for _ in 0..4 {
+        graph.draw(&mut frame, 0, 0, 1, 8, &[0]);
+        peaks.push(column(&frame, 0).iter().position(|on| *on));
+    }
+    assert_eq!(peaks, [Some(2), Some(2), Some(3), Some(4)]);
+}
+
+#[test]
+fn led_bar_rows_fill_digit_by_digit() {
+    assert_eq!(led_bar_rows(0), [0; 8]);
+    assert_eq!(led_bar_rows(9), [0xFF, 0x01, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(led_bar_rows(64), [0xFF; 8]);
+    assert_eq!(led_bar_rows(100), [0xFF; 8]);
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::{Event, EventLog, LoggedPin, MockSpi};
+use max7219_driver::builder::Max7219Builder;
+use max7219_driver::global::enums::{Chip, RegisterAddr};
+use max7219_driver::wiring::Wiring;
+
+#[test]
+fn skip_init_still_idles_cs() {
+    let log = EventLog::default();
+    let mut max7219 =
+        Max7219Builder::from_spi_cs_chip(1, log.clone(), LoggedPin(log.clone()), Chip::Max7221)
+            .skip_init()
+            .build()
+            .unwrap();
+    // no register is written, but CS is idle before the first frame
+    assert_eq!(log.events(), [Event::CsHigh]);
+
+    max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+    assert_eq!(
+        log.events()[1..],
+        [Event::CsLow, Event::Write(vec![1, 0x55]), Event::CsHigh]
+    );
+}
+
+#[test]
+fn skip_init_keeps_the_wiring() {
+    let spi = MockSpi::default();
+    let mut max7219 = Max7219Builder::from_spi(1, spi.clone())
+        .skip_init()
+        .wiring(Wiring::REVERSED)
+        .build()
+        .unwrap();
+    assert!(spi.writes().is_empty());
+
+    max7219.write_segments(0, 0, 0b0011_0000).unwrap();
+    assert_eq!(spi.registers(), [(0, 1, 0b0011_0000)]);
+}
@@ -0,0 +1,34 @@
+use max7219_driver::canvas::Canvas;
+use max7219_driver::matrix::{Framebuffer, Rect};
+
+fn lit(frame: &Framebuffer) -> usize {
+    (0..frame.height())
+        .flat_map(|y| (0..frame.width()).map(move |x| (x, y)))
+        .filter(|(x, y)| frame.pixel(*x, *y))
+        .count()
+}
+
+#[test]
+fn fill_rect_is_clipped_to_the_canvas() {
+    let mut frame = Framebuffer::new(2, 1).unwrap();
+    let mut canvas = Canvas::with_area(&mut frame, Rect::new(4, 0, 8, 8));
+    canvas.fill_rect(Rect::new(-1_000_000, 2, i32::MAX, 2), true);
+    assert_eq!(lit(&frame), 16);
+    assert!(frame.pixel(4, 2) && frame.pixel(11, 3));
+    assert!(!frame.pixel(3, 2) && !frame.pixel(12, 2));
+}
+
+#[test]
+fn line_with_far_away_ends() {
+    let mut frame = Framebuffer::new(1, 1).unwrap();
+    let mut canvas = Canvas::new(&mut frame);
+    canvas.line(i32::MIN, i32::MIN, i32::MIN, i32::MAX, true);
+    canvas.line(i32::MAX, -5, i32::MAX, 5, true);
+    canvas.line(i32::MIN, 0, i32::MAX, 0, false);
+    assert_eq!(lit(&frame), 0);
+
+    let mut canvas = Canvas::new(&mut frame);
+    canvas.line(0, 3, 7, 3, true);
+    canvas.line(7, 0, 0, 7, true);
+    assert_eq!(lit(&frame), 8 + 8 - 1);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::ShiftChain;
+use embedded_hal::blocking::spi::{Transfer, Write};
+use max7219_driver::global::enums::DriverError;
+use max7219_driver::MAX7219;
+
+/// MISO pulled high, e.g. with nothing wired back from the last device
+struct FloatingMiso;
+
+impl Write<u8> for FloatingMiso {
+    type Error = ();
+
+    fn write(&mut self, _words: &[u8]) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+impl Transfer<u8> for FloatingMiso {
+    type Error = ();
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ()> {
+        words.fill(0xFF);
+        Ok(words)
+    }
+}
+
+#[test]
+fn detects_every_supported_chain_length() {
+    for devices in 1..=8 {
+        let mut max7219 = MAX7219::from_spi(devices, ShiftChain::new(devices, 0x5A)).unwrap();
+        assert_eq!(max7219.detect_chain_length(), Ok(devices));
+        assert_eq!(max7219.verify_chain(), Ok(()));
+    }
+}
+
+#[test]
+fn detection_leaves_only_no_ops_in_the_chain() {
+    let mut max7219 = MAX7219::from_spi(4, ShiftChain::new(4, 0x5A)).unwrap();
+    max7219.detect_chain_length().unwrap();
+    let chain = max7219.into_inner().release();
+    assert!(chain.0.iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn verify_rejects_a_chain_of_another_length() {
+    let mut max7219 = MAX7219::from_spi(3, ShiftChain::new(2, 0x5A)).unwrap();
+    assert_eq!(max7219.detect_chain_length(), Ok(2));
+    assert_eq!(max7219.verify_chain(), Err(DriverError::Chain));
+}
+
+#[test]
+fn detection_fails_without_loopback() {
+    let mut max7219 = MAX7219::from_spi(2, FloatingMiso).unwrap();
+    assert_eq!(max7219.detect_chain_length(), Err(DriverError::Chain));
+    assert_eq!(max7219.verify_chain(), Err(DriverError::Chain));
+}
+
+#[test]
+fn detection_fails_past_max_displays() {
+    let mut max7219 = MAX7219::from_spi(8, ShiftChain::new(9, 0)).unwrap();
+    assert_eq!(max7219.detect_chain_length(), Err(DriverError::Chain));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::cell::RefCell;
+
+use common::{Event, EventLog, LoggedPin};
+use max7219_driver::global::enums::{Chip, Intensity, RegisterAddr, ScanLimit};
+use max7219_driver::MAX7219;
+
+#[test]
+fn max7221_cs_frames_every_write() {
+    let log = EventLog::default();
+    let mut max7219 =
+        MAX7219::from_spi_cs_chip(1, log.clone(), LoggedPin(log.clone()), Chip::Max7221).unwrap();
+    assert_eq!(log.events()[0], Event::CsHigh);
+    log.clear();
+    max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+    assert_eq!(
+        log.events(),
+        [Event::CsLow, Event::Write(vec![1, 0x55]), Event::CsHigh]
+    );
+}
+
+#[test]
+fn max7219_load_frames_every_write() {
+    let log = EventLog::default();
+    let mut max7219 =
+        MAX7219::from_spi_cs_chip(1, log.clone(), LoggedPin(log.clone()), Chip::Max7219).unwrap();
+    // init leaves LOAD alone, the first register write starts with it low
+    assert_eq!(log.events()[0], Event::CsLow);
+    log.clear();
+    max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+    assert_eq!(
+        log.events(),
+        [Event::CsLow, Event::Write(vec![1, 0x55]), Event::CsHigh]
+    );
+}
+
+#[test]
+fn shared_bus_frames_every_write() {
+    for (chip, first) in [
+        (Chip::Max7221, Event::CsHigh),
+        (Chip::Max7219, Event::CsLow),
+    ] {
+        let log = EventLog::default();
+        let bus = RefCell::new(log.clone());
+        let mut max7219 =
+            MAX7219::from_shared_spi_cs_chip(1, &bus, LoggedPin(log.clone()), chip).unwrap();
+        assert_eq!(log.events()[0], first);
+        log.clear();
+        max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+        assert_eq!(
+            log.events(),
+            [Event::CsLow, Event::Write(vec![1, 0x55]), Event::CsHigh]
+        );
+    }
+}
+
+#[test]
+fn duty_cycle_tables() {
+    assert_eq!(Chip::Max7219.duty_cycle_32(Intensity::Min), 1);
+    assert_eq!(Chip::Max7219.duty_cycle_32(Intensity::Max), 31);
+    assert_eq!(Chip::Max7221.duty_cycle_32(Intensity::Min), 2);
+    assert_eq!(Chip::Max7221.duty_cycle_32(Intensity::Ratio29_32), 30);
+    assert_eq!(Chip::Max7221.duty_cycle_32(Intensity::Max), 30);
+}
+
+#[test]
+fn compensation_uses_the_chip_duty_cycle() {
+    let full = ScanLimit::Display0To7;
+    let half = ScanLimit::Display0To3;
+    for chip in [Chip::Max7219, Chip::Max7221] {
+        assert_eq!(Intensity::Max.compensated_for(full, chip), Intensity::Max);
+    }
+    // 31/32 over half the digits: 15.5/32, closest to 15/32
+    assert_eq!(
+        Intensity::Max.compensated_for(half, Chip::Max7219),
+        Intensity::Ratio15_32
+    );
+    // 15/16 over half the digits: 7.5/16, as close to 7/16 (level 6) as to 8/16
+    // (level 7), the dimmer one is taken
+    assert_eq!(
+        Intensity::Max.compensated_for(half, Chip::Max7221),
+        Intensity::Ratio13_32
+    );
+    // 8/16 over half the digits is exactly 4/16, level 3
+    assert_eq!(
+        Intensity::Ratio15_32.compensated_for(half, Chip::Max7221),
+        Intensity::Ratio7_32
+    );
+}
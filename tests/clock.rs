@@ -0,0 +1,77 @@
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::clock::{
+    date, draw_hh_mm, draw_hh_mm_ss, hh_mm, hh_mm_ss, mm_ss_t, DateOrder,
+};
+
+#[test]
+fn times_roll_over_at_the_end_of_the_day() {
+    let text = hh_mm(23, 59, true).unwrap();
+    assert_eq!(&text.chars, b"    2359");
+    assert_eq!(text.dots, 0b0000_0100);
+    assert_eq!(&hh_mm(0, 0, false).unwrap().chars, b"    0000");
+    assert_eq!(hh_mm(0, 0, false).unwrap().dots, 0);
+    assert!(hh_mm(24, 0, true).is_err());
+    assert!(hh_mm(23, 60, true).is_err());
+
+    let text = hh_mm_ss(23, 59, 59, true).unwrap();
+    assert_eq!(&text.chars, b"  235959");
+    assert_eq!(text.dots, 0b0001_0100);
+    assert!(hh_mm_ss(23, 59, 60, true).is_err());
+}
+
+#[test]
+fn countdown_stops_below_a_hundred_minutes() {
+    let text = mm_ss_t(99 * 600 + 59 * 10 + 9, true).unwrap();
+    assert_eq!(&text.chars, b"   99599");
+    assert_eq!(text.dots, 0b0000_1010);
+    // the decimal point stays while the colon blinks
+    let text = mm_ss_t(0, false).unwrap();
+    assert_eq!(&text.chars, b"   00000");
+    assert_eq!(text.dots, 0b0000_0010);
+    assert!(mm_ss_t(100 * 600, true).is_err());
+}
+
+#[test]
+fn dates_show_the_last_two_digits_of_the_year() {
+    assert_eq!(
+        &date(31, 12, 1999, DateOrder::DayMonthYear).unwrap().chars,
+        b"31-12-99"
+    );
+    assert_eq!(
+        &date(1, 1, 2000, DateOrder::MonthDayYear).unwrap().chars,
+        b"01-01-00"
+    );
+    assert_eq!(
+        &date(5, 7, 2100, DateOrder::YearMonthDay).unwrap().chars,
+        b"00-07-05"
+    );
+    assert!(date(0, 1, 2000, DateOrder::DayMonthYear).is_err());
+    assert!(date(1, 13, 2000, DateOrder::DayMonthYear).is_err());
+}
+
+fn lit(frame: &Framebuffer) -> Vec<(i32, i32)> {
+    (0..frame.height())
+        .flat_map(|y| (0..frame.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| frame.pixel(x, y))
+        .collect()
+}
+
+#[test]
+fn blinking_colon_does_not_move_the_digits() {
+    for draw in [
+        |frame: &mut Framebuffer, colon| draw_hh_mm(frame, 0, 0, 12, 34, colon),
+        |frame: &mut Framebuffer, colon| draw_hh_mm_ss(frame, 0, 0, 12, 34, 56, colon),
+    ] {
+        let mut with_colon = Framebuffer::new(4, 1).unwrap();
+        let mut without_colon = Framebuffer::new(4, 1).unwrap();
+        draw(&mut with_colon, true).unwrap();
+        draw(&mut without_colon, false).unwrap();
+
+        let (with_colon, without_colon) = (lit(&with_colon), lit(&without_colon));
+        assert!(without_colon.len() < with_colon.len());
+        assert!(without_colon.iter().all(|pixel| with_colon.contains(pixel)));
+    }
+    let mut frame = Framebuffer::new(4, 1).unwrap();
+    assert!(draw_hh_mm(&mut frame, 0, 0, 24, 0, true).is_err());
+    assert!(lit(&frame).is_empty());
+}
@@ -0,0 +1,144 @@
+//!
+//! Host-side SPI mocks shared by the integration tests.
+//!
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::{Transfer, Write};
+use embedded_hal::digital::v2::OutputPin;
+
+/// Records every write, one entry per transfer
+#[derive(Clone, Default)]
+pub struct MockSpi(pub Rc<RefCell<Vec<Vec<u8>>>>);
+
+impl MockSpi {
+    pub fn writes(&self) -> Vec<Vec<u8>> {
+        self.0.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+
+    /// Every `(device_addr, register, data)` written, no-ops left out
+    pub fn registers(&self) -> Vec<(usize, u8, u8)> {
+        self.0
+            .borrow()
+            .iter()
+            .flat_map(|frame| {
+                frame
+                    .chunks(2)
+                    .enumerate()
+                    .filter(|(_, pair)| pair[0] != 0)
+                    .map(|(device_addr, pair)| (device_addr, pair[0], pair[1]))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl Write<u8> for MockSpi {
+    type Error = ();
+
+    fn write(&mut self, words: &[u8]) -> Result<(), ()> {
+        self.0.borrow_mut().push(words.to_vec());
+        Ok(())
+    }
+}
+
+/// A chain of shift registers with DOUT of the last device looped back to MISO
+pub struct ShiftChain(pub VecDeque<u8>);
+
+impl ShiftChain {
+    /// A chain of `devices` devices, each holding `fill` in both of its bytes
+    pub fn new(devices: usize, fill: u8) -> Self {
+        ShiftChain(vec![fill; devices * 2].into())
+    }
+}
+
+impl Write<u8> for ShiftChain {
+    type Error = ();
+
+    fn write(&mut self, words: &[u8]) -> Result<(), ()> {
+        for word in words {
+            self.0.push_back(*word);
+            self.0.pop_front();
+        }
+        Ok(())
+    }
+}
+
+impl Transfer<u8> for ShiftChain {
+    type Error = ();
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ()> {
+        for word in words.iter_mut() {
+            self.0.push_back(*word);
+            *word = self.0.pop_front().unwrap_or_default();
+        }
+        Ok(words)
+    }
+}
+
+/// A bus event, in the order it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Write(Vec<u8>),
+    CsHigh,
+    CsLow,
+    DelayUs(u32),
+}
+
+/// SPI bus and CS pin recording into one shared event log
+#[derive(Clone, Default)]
+pub struct EventLog(pub Rc<RefCell<Vec<Event>>>);
+
+impl EventLog {
+    pub fn events(&self) -> Vec<Event> {
+        self.0.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+
+impl Write<u8> for EventLog {
+    type Error = ();
+
+    fn write(&mut self, words: &[u8]) -> Result<(), ()> {
+        self.0.borrow_mut().push(Event::Write(words.to_vec()));
+        Ok(())
+    }
+}
+
+/// The CS pin side of an `EventLog`
+pub struct LoggedPin(pub EventLog);
+
+impl OutputPin for LoggedPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0 .0.borrow_mut().push(Event::CsLow);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0 .0.borrow_mut().push(Event::CsHigh);
+        Ok(())
+    }
+}
+
+/// The delay provider side of an `EventLog`
+pub struct LoggedDelay(pub EventLog);
+
+impl DelayUs<u32> for LoggedDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.0 .0.borrow_mut().push(Event::DelayUs(us));
+    }
+}
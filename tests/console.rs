@@ -0,0 +1,65 @@
+use core::fmt::Write;
+
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::console::TextConsole;
+
+#[test]
+fn carriage_return_overdraws_the_line() {
+    let mut console = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    write!(console, "8\r1").unwrap();
+
+    let mut expected = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    write!(expected, "1").unwrap();
+    assert_eq!(console.frame(), expected.frame());
+}
+
+#[test]
+fn carriage_return_keeps_the_rest_of_the_line() {
+    let mut console = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    write!(console, "88\r1").unwrap();
+
+    let mut expected = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    write!(expected, "18").unwrap();
+    assert_eq!(console.frame(), expected.frame());
+}
+
+fn lit_pixels(frame: &Framebuffer) -> usize {
+    (0..frame.height())
+        .flat_map(|y| (0..frame.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| frame.pixel(x, y))
+        .count()
+}
+
+#[test]
+fn writeln_keeps_the_line_on_a_single_line_console() {
+    let mut console = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    writeln!(console, "hi").unwrap();
+
+    let mut expected = TextConsole::new(Framebuffer::new(4, 1).unwrap()).unwrap();
+    write!(expected, "hi").unwrap();
+    assert!(lit_pixels(console.frame()) > 0);
+    assert_eq!(console.frame(), expected.frame());
+}
+
+#[test]
+fn writeln_fills_every_line_before_scrolling() {
+    let mut console = TextConsole::new(Framebuffer::new(4, 2).unwrap()).unwrap();
+    writeln!(console, "a").unwrap();
+    writeln!(console, "b").unwrap();
+
+    let mut expected = TextConsole::new(Framebuffer::new(4, 2).unwrap()).unwrap();
+    write!(expected, "a\nb").unwrap();
+    assert_eq!(console.frame(), expected.frame());
+
+    writeln!(console, "c").unwrap();
+    expected.clear();
+    write!(expected, "b\nc").unwrap();
+    assert_eq!(console.frame(), expected.frame());
+}
+
+#[test]
+fn consecutive_line_breaks_leave_blank_lines() {
+    let mut console = TextConsole::new(Framebuffer::new(2, 3).unwrap()).unwrap();
+    write!(console, "a\n\nb").unwrap();
+    assert_eq!(console.cursor().1, 2);
+}
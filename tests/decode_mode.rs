@@ -0,0 +1,40 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::global::enums::RegisterAddr;
+use max7219_driver::MAX7219;
+
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+// device addresses whose decode mode register was written
+fn decode_mode_targets(spi: &MockSpi) -> Vec<usize> {
+    spi.writes()
+        .iter()
+        .flat_map(|frame| {
+            frame
+                .chunks(2)
+                .enumerate()
+                .filter(|(_, pair)| pair[0] == DECODE_MODE)
+                .map(|(device_addr, _)| device_addr)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[test]
+fn write_str_sets_decode_mode_of_addressed_device() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    spi.clear();
+    max7219.write_str(1, b"12345678", 0).unwrap();
+    assert_eq!(decode_mode_targets(&spi), [1]);
+}
+
+#[test]
+fn write_bcd_sets_decode_mode_of_addressed_device() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    spi.clear();
+    max7219.write_bcd(1, b"12345678").unwrap();
+    assert_eq!(decode_mode_targets(&spi), [1]);
+}
@@ -0,0 +1,73 @@
+use max7219_driver::global::enums::*;
+
+// every value converts to its register byte and back, every other byte is rejected
+fn assert_round_trip<T>(all: &[T])
+where
+    T: Copy + PartialEq + core::fmt::Debug + TryFrom<u8, Error = DriverError> + Into<u8>,
+{
+    for value in all {
+        assert_eq!(T::try_from((*value).into()), Ok(*value));
+    }
+    let valid: Vec<u8> = all.iter().map(|value| (*value).into()).collect();
+    for byte in (0..=u8::MAX).filter(|byte| !valid.contains(byte)) {
+        assert_eq!(T::try_from(byte), Err(DriverError::InvalidValue));
+    }
+}
+
+#[test]
+fn register_values_round_trip() {
+    assert_round_trip(RegisterAddr::ALL);
+    assert_round_trip(Shutdown::ALL);
+    assert_round_trip(DecodeMode::ALL);
+    assert_round_trip(Intensity::ALL);
+    assert_round_trip(ScanLimit::ALL);
+    assert_round_trip(DisplayTest::ALL);
+}
+
+#[test]
+fn register_values_iterate_in_register_order() {
+    assert_eq!(Intensity::iter().count(), 16);
+    assert_eq!(u8::from(Intensity::iter().last().unwrap()), 0x0F);
+    assert_eq!(u8::from(RegisterAddr::ALL[0]), 0x00);
+    assert!(RegisterAddr::iter()
+        .map(u8::from)
+        .collect::<Vec<_>>()
+        .windows(2)
+        .all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn digit_registers() {
+    assert_eq!(RegisterAddr::digit(0), Ok(RegisterAddr::Digit0));
+    assert_eq!(RegisterAddr::digit(7), Ok(RegisterAddr::Digit7));
+    assert_eq!(RegisterAddr::digit(8), Err(DriverError::InvalidValue));
+}
+
+#[test]
+fn intensity_from_percent() {
+    assert_eq!(Intensity::from_percent(0), Ok(Intensity::Min));
+    assert_eq!(Intensity::from_percent(50), Ok(Intensity::Ratio17_32));
+    assert_eq!(Intensity::from_percent(100), Ok(Intensity::Max));
+    assert_eq!(Intensity::from_percent(101), Err(DriverError::InvalidValue));
+}
+
+#[test]
+fn intensity_percent_round_trips() {
+    assert_eq!(Intensity::Min.percent(), 0);
+    assert_eq!(Intensity::Max.percent(), 100);
+    for intensity in Intensity::iter() {
+        assert_eq!(Intensity::from_percent(intensity.percent()), Ok(intensity));
+    }
+}
+
+#[test]
+fn scan_limit_digit_count() {
+    for count in 1..=8 {
+        assert_eq!(
+            ScanLimit::from_digit_count(count).map(ScanLimit::digit_count),
+            Ok(count)
+        );
+    }
+    assert!(ScanLimit::from_digit_count(0).is_err());
+    assert!(ScanLimit::from_digit_count(9).is_err());
+}
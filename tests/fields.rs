@@ -0,0 +1,41 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::fields::SegmentFields;
+use max7219_driver::global::enums::RegisterAddr;
+use max7219_driver::layout::Align;
+use max7219_driver::MAX7219;
+
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+#[test]
+fn fields_transmit_only_their_digits() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let mut fields = SegmentFields::new();
+    let left = fields.add_field(1, 0..2, Align::Right).unwrap();
+    fields.add_field(1, 6..8, Align::Left).unwrap();
+    fields.write_str(left, "42").unwrap();
+    spi.clear();
+
+    fields.tick(&mut max7219).unwrap();
+    let registers = spi.registers();
+    // positions 0, 1, 6 and 7 are digit registers 8, 7, 2 and 1 with the standard wiring
+    let mut digits: Vec<u8> = registers.iter().skip(1).map(|(_, reg, _)| *reg).collect();
+    digits.sort();
+    assert_eq!(registers[0], (1, DECODE_MODE, 0x00));
+    assert_eq!(digits, [1, 2, 7, 8]);
+    assert!(registers
+        .iter()
+        .all(|(device_addr, _, _)| *device_addr == 1));
+
+    spi.clear();
+    fields.write_str(left, "7").unwrap();
+    fields.tick(&mut max7219).unwrap();
+    // the leading digit of the right aligned field is blanked, the other one changes
+    assert_eq!(spi.registers().len(), 2);
+
+    spi.clear();
+    fields.render(&mut max7219).unwrap();
+    assert_eq!(spi.registers().len(), 5);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::glyphs::{segment, segment14, FourteenSegment, GlyphTable, SevenSegment};
+use max7219_driver::MAX7219;
+
+#[test]
+fn seven_segment_k_has_no_upper_right_stroke() {
+    let k = SevenSegment.encode('K');
+    assert_eq!(k & segment::B, 0);
+    assert_eq!(
+        k,
+        segment::A | segment::C | segment::E | segment::F | segment::G
+    );
+}
+
+#[test]
+fn fourteen_segment_table() {
+    use segment14::*;
+    assert_eq!(FourteenSegment.encode('1'), B | C | J);
+    assert_eq!(FourteenSegment.encode('x'), FourteenSegment.encode('X'));
+    assert_eq!(FourteenSegment.encode('~'), FourteenSegment.unknown());
+    assert!(('A'..='Z').all(|c| FourteenSegment.glyph(c).is_some()));
+}
+
+#[test]
+fn write_str_14seg_spans_two_digits_per_character() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    spi.clear();
+    max7219.write_str_14seg(0, b"X   ").unwrap();
+    let writes = spi.writes();
+    // decode mode first, then digit positions 0 and 1 (registers 8 and 7) for the X
+    assert_eq!(writes[0], [0x09, 0x00]);
+    let [low, high] = FourteenSegment.encode('X').to_le_bytes();
+    assert_eq!(writes[1], [8, low]);
+    assert_eq!(writes[2], [7, high]);
+    assert_eq!(writes.len(), 1 + 8);
+}
@@ -0,0 +1,79 @@
+use max7219_driver::font::Font5x7;
+use max7219_driver::layout::{Align, TextLayout};
+use max7219_driver::matrix::{Framebuffer, Rect};
+
+fn drawn(layout: &TextLayout, modules_wide: usize, modules_high: usize, text: &str) -> Framebuffer {
+    let mut frame = Framebuffer::new(modules_wide, modules_high).unwrap();
+    let area = frame.bounds();
+    layout.draw(&mut frame, area, text);
+    frame
+}
+
+#[test]
+fn line_breaks_and_wrapping() {
+    let mut layout = TextLayout::new(Font5x7);
+    assert_eq!(layout.line_count("hello big world", 32), 1);
+    assert_eq!(layout.line_count("one\ntwo", 32), 2);
+    layout.set_wrap(true);
+    assert_eq!(layout.line_count("hello big world", 32), 3);
+    // a word wider than the line is broken within
+    assert_eq!(layout.line_count("abcdefghij", 32), 2);
+}
+
+#[test]
+fn trailing_line_break_adds_no_line() {
+    let layout = TextLayout::new(Font5x7);
+    assert_eq!(layout.line_count("one\n", 32), 1);
+    assert_eq!(layout.line_count("one\n\n", 32), 2);
+
+    let mut frame = Framebuffer::new(4, 2).unwrap();
+    let area = frame.bounds();
+    assert_eq!(layout.draw(&mut frame, area, "one\n"), 1);
+    assert_eq!(frame, drawn(&layout, 4, 2, "one"));
+}
+
+#[test]
+fn zero_width_holds_no_line() {
+    let mut layout = TextLayout::new(Font5x7);
+    layout.set_wrap(true);
+    assert_eq!(layout.line_count(" x", 0), 0);
+
+    let mut frame = Framebuffer::new(1, 1).unwrap();
+    assert_eq!(layout.draw(&mut frame, Rect::new(0, 0, 0, 8), "x"), 0);
+}
+
+#[test]
+fn too_wide_text_is_clipped_or_ends_in_ellipsis() {
+    let mut layout = TextLayout::new(Font5x7);
+    // clipped at the right edge of the area
+    let mut frame = Framebuffer::new(6, 1).unwrap();
+    layout.draw(&mut frame, Rect::new(0, 0, 12, 8), "Hello");
+    assert_eq!(frame, drawn(&layout, 6, 1, "He"));
+
+    layout.set_ellipsis(true);
+    let plain = TextLayout::new(Font5x7);
+    assert_eq!(
+        drawn(&layout, 6, 1, "Hello world"),
+        drawn(&plain, 6, 1, "Hello...")
+    );
+}
+
+#[test]
+fn ellipsis_marks_lines_that_do_not_fit() {
+    let mut layout = TextLayout::new(Font5x7);
+    layout.set_ellipsis(true);
+    let plain = TextLayout::new(Font5x7);
+    assert_eq!(
+        drawn(&layout, 6, 1, "Hi\nthere"),
+        drawn(&plain, 6, 1, "Hi...")
+    );
+}
+
+#[test]
+fn alignment() {
+    let mut layout = TextLayout::new(Font5x7);
+    layout.set_align(Align::Right);
+    let mut expected = Framebuffer::new(4, 1).unwrap();
+    expected.draw_text(32 - layout.measure("12"), 0, "12", &Font5x7);
+    assert_eq!(drawn(&layout, 4, 1, "12"), expected);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::global::enums::RegisterAddr;
+use max7219_driver::widgets::led_panel::{LedPanel, LinearMap};
+use max7219_driver::MAX7219;
+
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+#[test]
+fn first_flush_sets_no_decode_and_writes_every_digit() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let mut panel = LedPanel::new(LinearMap);
+    // LED 65 is bit 1 of Digit0 on the second device
+    panel.set_led(65, true).unwrap();
+    spi.clear();
+
+    panel.flush(&mut max7219).unwrap();
+    let registers = spi.registers();
+    assert_eq!(registers.len(), 2 * 9);
+    assert_eq!(registers[0], (0, DECODE_MODE, 0x00));
+    assert_eq!(registers[9], (1, DECODE_MODE, 0x00));
+    assert_eq!(registers[10], (1, 1, 0b0000_0010));
+}
+
+#[test]
+fn later_flushes_write_only_changed_digits() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let mut panel = LedPanel::new(LinearMap);
+    panel.flush(&mut max7219).unwrap();
+    spi.clear();
+
+    panel.set_group(&[8, 9, 10], true).unwrap();
+    panel.toggle_led(9).unwrap();
+    panel.flush(&mut max7219).unwrap();
+    assert_eq!(spi.registers(), [(0, 2, 0b0000_0101)]);
+
+    spi.clear();
+    panel.flush(&mut max7219).unwrap();
+    assert!(spi.registers().is_empty());
+}
+
+#[test]
+fn unmapped_leds_are_rejected() {
+    let table: &[_] = &[];
+    let mut panel = LedPanel::new(table);
+    assert!(panel.set_led(0, true).is_err());
+    let mut panel = LedPanel::new(LinearMap);
+    assert!(panel.set_group(&[0, 8 * 64], true).is_err());
+    assert_eq!(panel.led(0), Ok(false));
+}
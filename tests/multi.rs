@@ -0,0 +1,169 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::attributes::AttributedText;
+use max7219_driver::connectors::multi::MultiConnector;
+use max7219_driver::connectors::spi::SpiConnector;
+use max7219_driver::diagnostics::{Diagnostics, Layout, Step};
+use max7219_driver::fields::SegmentFields;
+use max7219_driver::global::enums::{
+    Chip, DecodeMode, DriverError, Intensity, RegisterAddr, ScanLimit,
+};
+use max7219_driver::layout::Align;
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::led_panel::{LedPanel, LinearMap};
+use max7219_driver::MAX7219;
+
+const INTENSITY: u8 = RegisterAddr::Intensity as u8;
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+type Sign = MAX7219<MultiConnector<SpiConnector<MockSpi>, SpiConnector<MockSpi>>, 16>;
+
+/// Two chains of 8 displays combined, with their cleared mocks
+fn sign() -> (Sign, MockSpi, MockSpi) {
+    let (a, b) = (MockSpi::default(), MockSpi::default());
+    let first = MAX7219::from_spi(8, a.clone()).unwrap();
+    let second = MAX7219::from_spi(8, b.clone()).unwrap();
+    let sign = MAX7219::from_chains(first, second).unwrap();
+    a.clear();
+    b.clear();
+    (sign, a, b)
+}
+
+#[test]
+fn combined_chains_beyond_max_displays() {
+    let (a, b) = (MockSpi::default(), MockSpi::default());
+    let first = MAX7219::from_spi(8, a.clone()).unwrap();
+    let mut second = MAX7219::from_spi_chip(8, b.clone(), Chip::Max7221).unwrap();
+    second.set_scan_limit(0, ScanLimit::Display0To3).unwrap();
+    second.set_intensity_compensation(true).unwrap();
+    let mut sign: MAX7219<_, 16> = MAX7219::from_chains(first, second).unwrap();
+    assert_eq!(sign.devices(), 16);
+    assert_eq!(sign.chip(3), Chip::Max7219);
+    assert_eq!(sign.chip(8), Chip::Max7221);
+    a.clear();
+    b.clear();
+
+    // the compensation of each chain is carried across
+    sign.set_intensity(0, Intensity::Max).unwrap();
+    sign.set_intensity(8, Intensity::Max).unwrap();
+    assert_eq!(a.registers(), [(0, INTENSITY, Intensity::Max as u8)]);
+    let compensated = Intensity::Max.compensated_for(ScanLimit::Display0To3, Chip::Max7221);
+    assert_ne!(compensated, Intensity::Max);
+    assert_eq!(b.registers(), [(0, INTENSITY, compensated as u8)]);
+    a.clear();
+    b.clear();
+
+    // module 12 is the fifth module of the lower row, device 4 of the second chain
+    let mut frame = Framebuffer::<16>::with_capacity(8, 2).unwrap();
+    frame.set_pixel(32, 8, true);
+    sign.write_frame(&frame).unwrap();
+    assert_eq!(a.writes().len(), 8);
+    assert_eq!(b.writes().len(), 8);
+    assert!(a.writes().iter().all(|frame| frame.len() == 16));
+    assert!(b
+        .registers()
+        .iter()
+        .all(|(device_addr, register, data)| *data == 0
+            || (*device_addr, *register, *data) == (4, 1, 0x80)));
+    assert!(b.registers().contains(&(4, 1, 0x80)));
+
+    let (first, second) = sign.split();
+    assert_eq!((first.devices(), second.devices()), (8, 8));
+}
+
+#[test]
+fn combined_chains_over_capacity_are_rejected() {
+    let first = MAX7219::from_spi(8, MockSpi::default()).unwrap();
+    let second = MAX7219::from_spi(1, MockSpi::default()).unwrap();
+    let combined: Result<MAX7219<_>, _> = MAX7219::from_chains(first, second);
+    assert!(combined.is_err());
+}
+
+#[test]
+fn attributed_text_reaches_every_display_of_a_long_chain() {
+    let (mut sign, a, b) = sign();
+    let mut text = AttributedText::<16>::with_capacity();
+    text.set_segments(12, 0, 0b0011_0000).unwrap();
+    assert!(text.set_segments(16, 0, 0).is_err());
+    text.tick(&mut sign).unwrap();
+    assert!(a.registers().is_empty());
+    assert_eq!(
+        b.registers(),
+        [
+            (4, DECODE_MODE, DecodeMode::NoDecode as u8),
+            (4, 8, 0b0011_0000)
+        ]
+    );
+}
+
+#[test]
+fn fields_reach_every_display_of_a_long_chain() {
+    let (mut sign, _, b) = sign();
+    let mut fields = SegmentFields::<16>::with_capacity();
+    let field = fields.add_field(15, 0..2, Align::Left).unwrap();
+    assert!(fields.add_field(16, 0..2, Align::Left).is_err());
+    fields.write_str(field, "1").unwrap();
+    fields.tick(&mut sign).unwrap();
+    assert!(b.registers().contains(&(7, 8, 0b0011_0000)));
+}
+
+#[test]
+fn led_panel_reaches_every_display_of_a_long_chain() {
+    let (mut sign, _, b) = sign();
+    let mut panel = LedPanel::<_, 16>::with_capacity(LinearMap);
+    // LED 10 * 64 is bit 0 of Digit0 on device 10
+    panel.set_led(10 * 64, true).unwrap();
+    assert!(panel.set_led(16 * 64, true).is_err());
+    panel.flush(&mut sign).unwrap();
+    assert!(b.registers().contains(&(2, 1, 0b0000_0001)));
+}
+
+#[test]
+fn digits_past_the_end_of_the_chain_are_rejected() {
+    let mut max7219 = MAX7219::from_spi(2, MockSpi::default()).unwrap();
+    let mut text = AttributedText::new();
+    text.set_segments(3, 0, 0xFF).unwrap();
+    assert_eq!(text.tick(&mut max7219), Err(DriverError::InvalidValue));
+
+    let mut panel = LedPanel::new(LinearMap);
+    panel.set_led(3 * 64, true).unwrap();
+    assert_eq!(panel.flush(&mut max7219), Err(DriverError::InvalidValue));
+}
+
+#[test]
+fn identify_labels_stay_unique_on_a_long_chain() {
+    let (mut sign, _, b) = sign();
+    let mut diagnostics = Diagnostics::new(Layout::SevenSegment);
+    while diagnostics.step(&mut sign).unwrap() != Some(Step::Identify) {}
+    // device 12 shows "dEv   12", "1" and "2" in the last two positions
+    let digits: Vec<(u8, u8)> = b
+        .registers()
+        .iter()
+        .filter(|(device_addr, register, _)| *device_addr == 4 && (1..=2).contains(register))
+        .map(|(_, register, data)| (*register, *data))
+        .collect();
+    assert_eq!(
+        digits[digits.len() - 2..],
+        [(2, 0b0011_0000), (1, 0b0110_1101)]
+    );
+}
+
+#[test]
+fn identify_draws_two_digit_addresses_on_matrix_modules() {
+    let (mut sign, _, b) = sign();
+    let mut diagnostics = Diagnostics::new(Layout::Matrix);
+    while diagnostics.step(&mut sign).unwrap() != Some(Step::Identify) {}
+    let rows = |device_addr: usize| -> Vec<u8> {
+        let registers = b.registers();
+        let rows: Vec<u8> = registers
+            .iter()
+            .filter(|(d, register, _)| *d == device_addr && (1..=8).contains(register))
+            .map(|(_, _, data)| *data)
+            .collect();
+        rows[rows.len() - 8..].to_vec()
+    };
+    // devices 10 and 12 are devices 2 and 4 of the second chain
+    assert_ne!(rows(2), rows(4));
+    assert!(rows(4).iter().any(|row| *row & 0x7F != 0));
+}
@@ -0,0 +1,18 @@
+use max7219_driver::global::enums::DriverError;
+use max7219_driver::stream::FrameStream;
+
+#[test]
+fn row_frame_encodes_every_device() {
+    let mut stream = FrameStream::new(2).unwrap();
+    stream
+        .encode_rows(&[[1, 2, 3, 4, 5, 6, 7, 8], [9; 8]])
+        .unwrap();
+    assert_eq!(stream.row_frame(0), Ok(&[1, 1, 1, 9][..]));
+    assert_eq!(stream.row_frame(7), Ok(&[8, 8, 8, 9][..]));
+}
+
+#[test]
+fn row_frame_rejects_rows_past_the_last_digit() {
+    let stream = FrameStream::new(2).unwrap();
+    assert_eq!(stream.row_frame(8), Err(DriverError::InvalidValue));
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use core::fmt::Write;
+
+use common::MockSpi;
+use max7219_driver::global::enums::RegisterAddr;
+use max7219_driver::glyphs::{segment, GlyphTable, SevenSegment};
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::text::{MatrixWriter, SegmentWriter};
+use max7219_driver::MAX7219;
+
+const DECODE_MODE: u8 = RegisterAddr::DecodeMode as u8;
+
+/// The digits written by a flush, from the leftmost position on
+fn flushed_digits(spi: &MockSpi) -> Vec<u8> {
+    let registers = spi.registers();
+    assert_eq!(registers[0], (0, DECODE_MODE, 0x00));
+    // position p is digit register 8 - p with the standard wiring
+    let mut digits = [0; 8];
+    for (_, register, data) in &registers[1..] {
+        digits[8 - *register as usize] = *data;
+    }
+    digits.to_vec()
+}
+
+#[test]
+fn dot_is_folded_into_the_preceding_digit() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = SegmentWriter::new(&mut max7219, 0);
+    write!(text, "12.5").unwrap();
+    spi.clear();
+    text.flush().unwrap();
+
+    let mut expected = [0; 8];
+    expected[0] = SevenSegment.encode('1');
+    expected[1] = SevenSegment.encode('2') | segment::DP;
+    expected[2] = SevenSegment.encode('5');
+    assert_eq!(flushed_digits(&spi), expected);
+}
+
+#[test]
+fn dot_takes_a_digit_of_its_own_when_needed() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = SegmentWriter::new(&mut max7219, 0);
+    // a leading dot and a second dot have no digit to fold into
+    write!(text, ".1..").unwrap();
+    spi.clear();
+    text.flush().unwrap();
+
+    let dot = SevenSegment.encode('.');
+    let mut expected = [0; 8];
+    expected[0] = dot;
+    expected[1] = SevenSegment.encode('1') | segment::DP;
+    expected[2] = dot;
+    assert_eq!(flushed_digits(&spi), expected);
+}
+
+#[test]
+fn writing_past_the_last_digit_fails() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = SegmentWriter::new(&mut max7219, 0);
+    write!(text, "12345678").unwrap();
+    // a dot still folds into the last digit
+    write!(text, ".").unwrap();
+    assert!(write!(text, "9").is_err());
+
+    text.clear();
+    assert!(write!(text, "1.2.3.4.5.6.7.8.").is_ok());
+    assert!(write!(text, "9").is_err());
+}
+
+#[test]
+fn flush_blanks_the_digits_left_over_and_empties_the_buffer() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = SegmentWriter::new(&mut max7219, 0);
+    write!(text, "12345678").unwrap();
+    text.flush().unwrap();
+    write!(text, "1").unwrap();
+    spi.clear();
+    text.flush().unwrap();
+
+    let mut expected = [0; 8];
+    expected[0] = SevenSegment.encode('1');
+    assert_eq!(flushed_digits(&spi), expected);
+}
+
+#[test]
+fn matrix_text_is_clipped_at_the_right_edge() {
+    let mut max7219 = MAX7219::from_spi(1, MockSpi::default()).unwrap();
+    let mut text = MatrixWriter::new(&mut max7219, Framebuffer::new(1, 1).unwrap());
+    // only the first two characters reach into the 8 pixel wide module
+    write!(text, "12").unwrap();
+    let visible = text.frame().clone();
+    text.clear();
+    assert!(write!(text, "1234567890").is_ok());
+    assert_eq!(text.frame(), &visible);
+}
+
+#[test]
+fn matrix_text_ignores_control_characters() {
+    let mut max7219 = MAX7219::from_spi(1, MockSpi::default()).unwrap();
+    let mut text = MatrixWriter::new(&mut max7219, Framebuffer::new(1, 1).unwrap());
+    write!(text, "1\n\r2").unwrap();
+    let with_controls = text.frame().clone();
+    text.clear();
+    write!(text, "12").unwrap();
+    assert_eq!(text.frame(), &with_controls);
+}
+
+#[test]
+fn matrix_flush_writes_the_frame_and_starts_over() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(1, spi.clone()).unwrap();
+    let mut text = MatrixWriter::new(&mut max7219, Framebuffer::new(1, 1).unwrap());
+    write!(text, "1").unwrap();
+    let drawn = text.frame().clone();
+    spi.clear();
+    text.flush().unwrap();
+
+    let rows: Vec<u8> = spi.registers().iter().map(|(_, _, data)| *data).collect();
+    assert_eq!(rows, drawn.device_rows(0));
+    assert_eq!(text.frame(), &Framebuffer::new(1, 1).unwrap());
+}
@@ -0,0 +1,116 @@
+use max7219_driver::font::Font5x7;
+use max7219_driver::layout::{CharStyle, TextLayout};
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::text_effects::{TextEffect, TextEffects};
+
+fn at_tick(effects: &mut TextEffects, tick: u32) -> &TextEffects {
+    effects.restart();
+    for _ in 0..tick {
+        effects.tick();
+    }
+    effects
+}
+
+fn hidden(effects: &TextEffects, chars: usize) -> Vec<bool> {
+    (0..chars)
+        .map(|index| effects.style(index).hidden)
+        .collect()
+}
+
+#[test]
+fn typewriter_shows_one_more_character_every_ticks() {
+    let mut effects = TextEffects::new();
+    effects
+        .add_span(1..4, TextEffect::Typewriter { ticks: 2 })
+        .unwrap();
+    // the character before the span is not affected
+    assert_eq!(
+        hidden(at_tick(&mut effects, 0), 4),
+        [false, false, true, true]
+    );
+    assert_eq!(
+        hidden(at_tick(&mut effects, 1), 4),
+        [false, false, true, true]
+    );
+    assert_eq!(
+        hidden(at_tick(&mut effects, 2), 4),
+        [false, false, false, true]
+    );
+    assert_eq!(hidden(at_tick(&mut effects, 4), 4), [false; 4]);
+}
+
+#[test]
+fn blink_alternates_every_ticks() {
+    let mut effects = TextEffects::new();
+    effects
+        .add_span(0..1, TextEffect::Blink { ticks: 3 })
+        .unwrap();
+    let shown: Vec<bool> = (0..8)
+        .map(|tick| !at_tick(&mut effects, tick).style(0).hidden)
+        .collect();
+    assert_eq!(shown, [true, true, true, false, false, false, true, true]);
+}
+
+#[test]
+fn bounce_moves_each_character_one_pixel_ahead_of_the_previous_one() {
+    let mut effects = TextEffects::new();
+    effects
+        .add_span(
+            0..4,
+            TextEffect::Bounce {
+                height: 2,
+                ticks: 1,
+            },
+        )
+        .unwrap();
+    let offsets = |effects: &TextEffects| -> Vec<i32> {
+        (0..4).map(|index| effects.style(index).offset_y).collect()
+    };
+    assert_eq!(offsets(at_tick(&mut effects, 0)), [0, -1, -2, -1]);
+    assert_eq!(offsets(at_tick(&mut effects, 1)), [-1, 0, -1, -2]);
+    assert_eq!(offsets(at_tick(&mut effects, 2)), [-2, -1, 0, -1]);
+    // the wave repeats every 2 * height ticks
+    assert_eq!(offsets(at_tick(&mut effects, 4)), [0, -1, -2, -1]);
+}
+
+#[test]
+fn overlapping_spans_are_combined() {
+    let mut effects = TextEffects::new();
+    effects.add_span(0..2, TextEffect::Inverted).unwrap();
+    effects
+        .add_span(1..3, TextEffect::Blink { ticks: 1 })
+        .unwrap();
+    let effects = at_tick(&mut effects, 1);
+    assert_eq!(
+        effects.style(1),
+        CharStyle {
+            hidden: true,
+            inverted: true,
+            offset_y: 0
+        }
+    );
+    assert_eq!(effects.style(3), CharStyle::default());
+}
+
+#[test]
+fn hidden_characters_keep_their_space() {
+    let layout = TextLayout::new(Font5x7);
+    let mut effects = TextEffects::new();
+    effects
+        .add_span(0..1, TextEffect::Blink { ticks: 1 })
+        .unwrap();
+    let area = Framebuffer::new(4, 1).unwrap().bounds();
+
+    let mut shown = Framebuffer::new(4, 1).unwrap();
+    effects.draw(&layout, &mut shown, area, "1:2");
+    let mut plain = Framebuffer::new(4, 1).unwrap();
+    layout.draw(&mut plain, area, "1:2");
+    assert_eq!(shown, plain);
+
+    effects.tick();
+    let mut blinked = Framebuffer::new(4, 1).unwrap();
+    effects.draw(&layout, &mut blinked, area, "1:2");
+    let mut expected = Framebuffer::new(4, 1).unwrap();
+    layout.draw(&mut expected, area, " :2");
+    assert_eq!(blinked, expected);
+}
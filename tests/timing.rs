@@ -0,0 +1,126 @@
+mod common;
+
+use common::{Event, EventLog, LoggedDelay, LoggedPin};
+use max7219_driver::connectors::timing::CsTiming;
+use max7219_driver::global::enums::{Chip, RegisterAddr, Shutdown};
+use max7219_driver::MAX7219;
+
+const TIMING: CsTiming = CsTiming {
+    cs_setup_ns: 1500,
+    cs_hold_ns: 2000,
+    cs_high_ns: 50,
+};
+
+#[test]
+fn cs_setup_hold_and_high_times_are_waited() {
+    let log = EventLog::default();
+    let mut max7219 = MAX7219::from_spi_cs_timed(
+        1,
+        log.clone(),
+        LoggedPin(log.clone()),
+        Chip::Max7219,
+        LoggedDelay(log.clone()),
+        TIMING,
+    )
+    .unwrap();
+    log.clear();
+
+    max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+    // delays are rounded up to whole microseconds
+    assert_eq!(
+        log.events(),
+        [
+            Event::CsLow,
+            Event::DelayUs(2),
+            Event::Write(vec![1, 0x55]),
+            Event::DelayUs(2),
+            Event::CsHigh,
+            Event::DelayUs(1),
+        ]
+    );
+}
+
+#[test]
+fn zero_timings_are_not_waited() {
+    let log = EventLog::default();
+    let timing = CsTiming {
+        cs_setup_ns: 0,
+        cs_hold_ns: 0,
+        cs_high_ns: 0,
+    };
+    let mut max7219 = MAX7219::from_spi_cs_timed(
+        1,
+        log.clone(),
+        LoggedPin(log.clone()),
+        Chip::Max7219,
+        LoggedDelay(log.clone()),
+        timing,
+    )
+    .unwrap();
+    log.clear();
+
+    max7219.write_raw(0, RegisterAddr::Digit0, 0x55).unwrap();
+    assert_eq!(
+        log.events(),
+        [Event::CsLow, Event::Write(vec![1, 0x55]), Event::CsHigh]
+    );
+}
+
+#[test]
+fn max7221_cs_high_time_is_waited_on_init() {
+    let log = EventLog::default();
+    MAX7219::from_spi_cs_timed(
+        1,
+        log.clone(),
+        LoggedPin(log.clone()),
+        Chip::Max7221,
+        LoggedDelay(log.clone()),
+        TIMING,
+    )
+    .unwrap();
+    assert_eq!(log.events()[..2], [Event::CsHigh, Event::DelayUs(1)]);
+}
+
+#[test]
+fn release_returns_the_delay_provider() {
+    let log = EventLog::default();
+    let max7219 = MAX7219::from_spi_cs_timed(
+        1,
+        log.clone(),
+        LoggedPin(log.clone()),
+        Chip::Max7219,
+        LoggedDelay(log.clone()),
+        TIMING,
+    )
+    .unwrap();
+    log.clear();
+
+    let (_, _, mut delay) = max7219.release_with_delay();
+    assert!(log.events().is_empty());
+    embedded_hal::blocking::delay::DelayUs::delay_us(&mut delay, 7);
+    assert_eq!(log.events(), [Event::DelayUs(7)]);
+}
+
+#[test]
+fn shutdown_and_release_returns_the_delay_provider() {
+    let log = EventLog::default();
+    let max7219 = MAX7219::from_spi_cs_timed(
+        1,
+        log.clone(),
+        LoggedPin(log.clone()),
+        Chip::Max7219,
+        LoggedDelay(log.clone()),
+        TIMING,
+    )
+    .unwrap();
+    log.clear();
+
+    let (_, _, mut delay) = max7219.shutdown_and_release_with_delay().ok().unwrap();
+    assert!(log.events().contains(&Event::Write(vec![
+        RegisterAddr::Shutdown as u8,
+        Shutdown::ShutdownMode as u8
+    ])));
+    log.clear();
+    embedded_hal::blocking::delay::DelayUs::delay_us(&mut delay, 7);
+    assert_eq!(log.events(), [Event::DelayUs(7)]);
+}
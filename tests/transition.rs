@@ -0,0 +1,123 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::global::enums::{Intensity, RegisterAddr};
+use max7219_driver::matrix::Framebuffer;
+use max7219_driver::widgets::transition::{Direction, Effect, Transition};
+use max7219_driver::MAX7219;
+
+const INTENSITY: u8 = RegisterAddr::Intensity as u8;
+
+fn pages() -> (Framebuffer, Framebuffer) {
+    let mut from = Framebuffer::new(2, 1).unwrap();
+    let mut to = Framebuffer::new(2, 1).unwrap();
+    for x in 0..16 {
+        from.set_pixel(x, x % 8, true);
+        to.set_pixel(x, 7 - x % 8, true);
+        to.set_pixel(x, 3, x % 3 == 0);
+    }
+    (from, to)
+}
+
+/// The chain writes of showing `frame` on its own
+fn frame_writes(frame: &Framebuffer) -> Vec<Vec<u8>> {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    spi.clear();
+    max7219.write_frame(frame).unwrap();
+    spi.writes()
+}
+
+#[test]
+fn every_effect_ends_on_the_new_frame() {
+    let (from, to) = pages();
+    for effect in [
+        Effect::Slide(Direction::Left),
+        Effect::Slide(Direction::Right),
+        Effect::Slide(Direction::Up),
+        Effect::Slide(Direction::Down),
+        Effect::Wipe(Direction::Left),
+        Effect::Wipe(Direction::Right),
+        Effect::Wipe(Direction::Up),
+        Effect::Wipe(Direction::Down),
+        Effect::Dissolve,
+        Effect::ScrollUp,
+        Effect::Curtain,
+    ] {
+        let spi = MockSpi::default();
+        let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+        let mut transition = Transition::new(from.clone(), to.clone(), effect).unwrap();
+        let mut ticks = 1;
+        while transition.tick(&mut max7219).unwrap() {
+            ticks += 1;
+        }
+        assert_eq!(ticks, transition.steps(), "{:?}", effect);
+        assert!(transition.is_finished());
+        assert_eq!(transition.frame(), &to, "{:?}", effect);
+
+        let writes = spi.writes();
+        assert_eq!(
+            writes[writes.len() - 8..],
+            frame_writes(&to),
+            "{:?}",
+            effect
+        );
+        // nothing more is shown once finished
+        spi.clear();
+        assert!(!transition.tick(&mut max7219).unwrap());
+        assert!(spi.writes().is_empty());
+    }
+}
+
+#[test]
+fn slide_moves_both_frames_one_column_per_step() {
+    let (from, to) = pages();
+    let mut max7219 = MAX7219::from_spi(2, MockSpi::default()).unwrap();
+    let mut transition =
+        Transition::new(from.clone(), to.clone(), Effect::Slide(Direction::Left)).unwrap();
+    for _ in 0..4 {
+        transition.tick(&mut max7219).unwrap();
+    }
+    for y in 0..8 {
+        assert_eq!(transition.frame().pixel(0, y), from.pixel(4, y));
+        assert_eq!(transition.frame().pixel(12, y), to.pixel(0, y));
+    }
+}
+
+#[test]
+fn fade_ends_on_the_new_frame_at_the_given_intensity() {
+    let (from, to) = pages();
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let mut transition =
+        Transition::new(from, to.clone(), Effect::Fade(Intensity::Ratio7_32)).unwrap();
+    spi.clear();
+    transition.run(&mut max7219, &mut NoWait, 0).unwrap();
+
+    let writes = spi.writes();
+    let intensities: Vec<u8> = spi
+        .registers()
+        .iter()
+        .filter(|(_, register, _)| *register == INTENSITY)
+        .map(|(_, _, data)| *data)
+        .collect();
+    // down from level 3 to 0 on the old frame, then up to 3 on the new one,
+    // both devices being set at once
+    let levels: Vec<u8> = intensities.chunks(2).map(|pair| pair[0]).collect();
+    assert_eq!(levels, [3, 2, 1, 0, 0, 1, 2, 3]);
+    assert_eq!(*levels.last().unwrap(), Intensity::Ratio7_32 as u8);
+    assert_eq!(writes[writes.len() - 8..], frame_writes(&to));
+}
+
+#[test]
+fn frames_of_different_sizes_are_rejected() {
+    let from = Framebuffer::new(2, 1).unwrap();
+    let to = Framebuffer::new(1, 2).unwrap();
+    assert!(Transition::new(from, to, Effect::Dissolve).is_err());
+}
+
+struct NoWait;
+
+impl embedded_hal::blocking::delay::DelayMs<u32> for NoWait {
+    fn delay_ms(&mut self, _ms: u32) {}
+}
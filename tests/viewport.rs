@@ -0,0 +1,33 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::matrix::{Framebuffer, Rect};
+use max7219_driver::viewport::Compositor;
+use max7219_driver::MAX7219;
+
+#[test]
+fn flush_batches_changes_per_row() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(3, spi.clone()).unwrap();
+    let mut compositor = Compositor::new(Framebuffer::new(3, 1).unwrap());
+    let all = compositor
+        .add_viewport("all", Rect::new(0, 0, 24, 8), 1)
+        .unwrap();
+    compositor.flush(&mut max7219).unwrap();
+    spi.clear();
+
+    let mut canvas = compositor.canvas(all);
+    canvas.set_pixel(0, 0, true);
+    canvas.set_pixel(16, 0, true);
+    canvas.set_pixel(8, 5, true);
+    compositor.flush(&mut max7219).unwrap();
+    // one chain write per changed row, no-ops for the devices whose row is unchanged
+    assert_eq!(
+        spi.writes(),
+        [vec![1, 0x80, 0, 0, 1, 0x80], vec![0, 0, 6, 0x80, 0, 0]]
+    );
+
+    spi.clear();
+    compositor.flush(&mut max7219).unwrap();
+    assert!(spi.writes().is_empty());
+}
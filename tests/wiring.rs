@@ -0,0 +1,73 @@
+mod common;
+
+use common::MockSpi;
+use max7219_driver::global::enums::DriverError;
+use max7219_driver::wiring::Wiring;
+use max7219_driver::MAX7219;
+
+#[test]
+fn standard_and_reversed_digit_order() {
+    let registers = |wiring: Wiring| -> Vec<u8> {
+        (0..8)
+            .map(|position| wiring.digit_register(position).unwrap())
+            .collect()
+    };
+    assert_eq!(registers(Wiring::STANDARD), [8, 7, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(registers(Wiring::REVERSED), [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn digit_register_rejects_positions_past_the_last_digit() {
+    assert_eq!(
+        Wiring::STANDARD.digit_register(8),
+        Err(DriverError::InvalidValue)
+    );
+}
+
+#[test]
+fn custom_permutations_are_applied() {
+    let wiring = Wiring::STANDARD
+        .with_digit_order([3, 2, 1, 0, 7, 6, 5, 4])
+        .unwrap()
+        // DP and G swapped
+        .with_segment_order([7, 1, 2, 3, 4, 5, 6, 0])
+        .unwrap();
+    assert_eq!(wiring.digit_register(0), Ok(4));
+    assert_eq!(wiring.digit_register(4), Ok(8));
+    assert_eq!(wiring.map_segments(0b1000_0000), 0b0000_0001);
+    assert_eq!(wiring.map_segments(0b0000_0001), 0b1000_0000);
+    assert_eq!(wiring.map_segments(0b0111_1110), 0b0111_1110);
+}
+
+#[test]
+fn non_permutations_are_rejected() {
+    for digits in [[0, 0, 1, 2, 3, 4, 5, 6], [0, 1, 2, 3, 4, 5, 6, 8]] {
+        assert_eq!(
+            Wiring::STANDARD.with_digit_order(digits),
+            Err(DriverError::InvalidValue)
+        );
+        assert_eq!(
+            Wiring::STANDARD.with_segment_order(digits),
+            Err(DriverError::InvalidValue)
+        );
+    }
+}
+
+#[test]
+fn segments_are_written_through_the_device_wiring() {
+    let spi = MockSpi::default();
+    let mut max7219 = MAX7219::from_spi(2, spi.clone()).unwrap();
+    let wiring = Wiring::REVERSED
+        .with_segment_order([7, 1, 2, 3, 4, 5, 6, 0])
+        .unwrap();
+    max7219.set_wiring(1, wiring).unwrap();
+    spi.clear();
+
+    max7219.write_segments(1, 0, 0b1000_0001).unwrap();
+    max7219.write_segments(0, 0, 0b1000_0000).unwrap();
+    assert_eq!(spi.registers(), [(1, 1, 0b1000_0001), (0, 8, 0b1000_0000)]);
+    assert_eq!(
+        max7219.write_segments(0, 8, 0),
+        Err(DriverError::InvalidValue)
+    );
+}